@@ -0,0 +1,943 @@
+//! Benchmarking setup for pallet-certificates
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Currency, EnsureOrigin},
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Saturating, StaticLookup, Zero};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Origin accepted by `IssuerOrigin` and the issuer account it resolves to
+fn issuer_origin<T: Config>() -> (T::RuntimeOrigin, T::AccountId) {
+    let origin = T::IssuerOrigin::try_successful_origin()
+        .expect("IssuerOrigin has no successful origin required for the benchmark");
+    let issuer = T::IssuerOrigin::ensure_origin(origin.clone())
+        .expect("successful origin is accepted by IssuerOrigin");
+    (origin, issuer)
+}
+
+/// Origin accepted by `RegistrarOrigin`
+fn registrar_origin<T: Config>() -> T::RuntimeOrigin {
+    T::RegistrarOrigin::try_successful_origin()
+        .expect("RegistrarOrigin has no successful origin required for the benchmark")
+}
+
+/// Origin accepted by `ForceOrigin`
+fn force_origin<T: Config>() -> T::RuntimeOrigin {
+    T::ForceOrigin::try_successful_origin().expect("ForceOrigin has no successful origin required for the benchmark")
+}
+
+/// Certificate type requiring a `name` field
+fn type_info<T: Config>() -> CertificateTypeInfo<T> {
+    CertificateTypeInfo {
+        name: BoundedVec::truncate_from(b"Benchmark".to_vec()),
+        default_validity: Zero::zero(),
+        required_fields: BoundedVec::truncate_from(vec![BoundedVec::truncate_from(b"name".to_vec())]),
+    }
+}
+
+/// Accreditation body registry entry with a maximum-length name
+fn accreditor_info<T: Config>() -> AccreditorInfo<T> {
+    AccreditorInfo {
+        name: BoundedVec::truncate_from(vec![b'a'; T::MaxIssuerFieldLength::get() as usize]),
+        registered_at: Zero::zero(),
+    }
+}
+
+/// Register the maximum number of certificate types, each with an issuance fee and requiring
+/// accreditation, and a funded issuer allowed to issue and accredited for all of them, returning
+/// the type found last in the issuer's list
+fn setup_issuer<T: Config>(issuer: &T::AccountId) -> T::CertificateTypeId {
+    let max_types = T::MaxCertificateTypesPerIssuer::get();
+    let accreditor: T::AccountId = account("accreditor", 0, SEED);
+    Accreditors::<T>::insert(&accreditor, accreditor_info::<T>());
+    let accreditation = Accreditation { accreditor, granted_at: Pallet::<T>::now(), expires_at: later::<T>() };
+    let types = (0..max_types)
+        .map(|i| {
+            let type_id = T::CertificateTypeId::from(i);
+            CertificateTypes::<T>::insert(type_id, type_info::<T>());
+            IssuanceFees::<T>::insert(type_id, T::Currency::minimum_balance());
+            AccreditationRequired::<T>::insert(type_id, ());
+            Accreditations::<T>::insert(issuer, type_id, accreditation.clone());
+            type_id
+        })
+        .collect::<Vec<_>>();
+    T::Currency::make_free_balance_be(issuer, BalanceOf::<T>::max_value() / 2u32.into());
+    let field = BoundedVec::truncate_from(vec![b'a'; T::MaxIssuerFieldLength::get() as usize]);
+
+    Issuers::<T>::insert(
+        issuer,
+        IssuerInfo {
+            legal_name: field.clone(),
+            accreditation_number: field.clone(),
+            website: field,
+            certificate_types: BoundedVec::truncate_from(types),
+            status: IssuerStatus::Active,
+            registered_at: Zero::zero(),
+        },
+    );
+
+    T::CertificateTypeId::from(max_types.saturating_sub(1))
+}
+
+/// JSON metadata of `len` bytes (at least the 11 bytes of `{"name":""}`)
+fn metadata(len: u32) -> Vec<u8> {
+    let mut metadata = b"{\"name\":\"".to_vec();
+    metadata.resize((len as usize).max(11) - 2, b'a');
+    metadata.extend_from_slice(b"\"}");
+    metadata
+}
+
+/// Fill an account's certificate list to one below its bound
+fn fill_account<T: Config>(account: &T::AccountId) {
+    let ids = (0..T::MaxCertificatesPerAccount::get().saturating_sub(1))
+        .map(|i| T::CertificateId::from(u32::MAX - i))
+        .collect::<Vec<_>>();
+    AccountCertificates::<T>::insert(account, BoundedVec::truncate_from(ids));
+}
+
+/// Issue a certificate with maximum-length metadata expiring at `expires_at`
+fn issue<T: Config>(
+    issuer: &T::AccountId,
+    recipient: &T::AccountId,
+    cert_type: T::CertificateTypeId,
+    expires_at: Moment,
+) -> T::CertificateId {
+    Pallet::<T>::do_issue(
+        issuer,
+        issuer,
+        recipient,
+        cert_type,
+        CertificateContent::Inline(BoundedVec::truncate_from(metadata(T::MaxMetadataLength::get()))),
+        Some(expires_at),
+        None,
+    )
+    .expect("benchmark certificate can be issued")
+}
+
+/// Offer a certificate with maximum-length metadata through the issuer's origin
+fn offer<T: Config>(
+    origin: T::RuntimeOrigin,
+    recipient: &T::AccountId,
+    cert_type: T::CertificateTypeId,
+) -> T::CertificateId {
+    let id = CertificateCount::<T>::get();
+    Pallet::<T>::offer_cert(
+        origin,
+        T::Lookup::unlookup(recipient.clone()),
+        cert_type,
+        metadata(T::MaxMetadataLength::get()),
+        Some(later::<T>()),
+    )
+    .expect("benchmark certificate can be offered");
+    id
+}
+
+/// Record `count` endorsements from funded endorsers against a certificate
+fn endorse<T: Config>(cert_id: T::CertificateId, count: u32) {
+    for i in 0..count {
+        let endorser: T::AccountId = account("endorser", i, SEED);
+        T::Currency::make_free_balance_be(&endorser, BalanceOf::<T>::max_value() / 2u32.into());
+        Pallet::<T>::endorse_cert(RawOrigin::Signed(endorser).into(), cert_id, T::Hash::default())
+            .expect("benchmark endorsement can be recorded");
+    }
+}
+
+/// Register `r` certificate types and require each of them with some validity left
+fn site_requirements<T: Config>(r: u32) -> Vec<(T::CertificateTypeId, Moment)> {
+    (0..r)
+        .map(|i| {
+            let type_id = T::CertificateTypeId::from(i);
+            CertificateTypes::<T>::insert(type_id, type_info::<T>());
+            (type_id, 1)
+        })
+        .collect()
+}
+
+/// Create a site profile with the maximum number of requirements
+fn setup_site<T: Config>(owner: &T::AccountId) -> T::SiteId {
+    let site_id = SiteCount::<T>::get();
+    let requirements = site_requirements::<T>(T::MaxSiteRequirements::get())
+        .into_iter()
+        .map(|(cert_type, min_remaining_validity)| SiteRequirement { cert_type, min_remaining_validity })
+        .collect::<Vec<_>>();
+    SiteProfiles::<T>::insert(
+        site_id,
+        SiteProfile {
+            owner: owner.clone(),
+            name: BoundedVec::truncate_from(vec![b'a'; T::MaxSiteNameLength::get() as usize]),
+            requirements: BoundedVec::truncate_from(requirements),
+            deposit: Zero::zero(),
+        },
+    );
+    SiteCount::<T>::put(T::SiteId::from(Into::<u32>::into(site_id) + 1));
+    site_id
+}
+
+/// Give an issuer `count` delegates
+fn fill_delegates<T: Config>(issuer: &T::AccountId, count: u32) {
+    let delegates = (0..count).map(|i| account("delegate", i, SEED)).collect::<Vec<T::AccountId>>();
+    for delegate in delegates.iter() {
+        Delegates::<T>::insert(delegate, issuer);
+    }
+    IssuerDelegates::<T>::insert(issuer, BoundedVec::truncate_from(delegates));
+}
+
+/// Fill an issuer's key history, ending with `key` as its current signing key
+fn setup_key_history<T: Config>(issuer: &T::AccountId, key: &T::AccountId) {
+    let max = T::MaxKeyHistory::get();
+    let history = (0..max)
+        .map(|i| IssuerKey {
+            key: if i + 1 == max { key.clone() } else { account("old_key", i, SEED) },
+            valid_from: i.into(),
+            valid_until: (i + 1 < max).then(|| (i + 1).into()),
+        })
+        .collect::<Vec<_>>();
+    for entry in history.iter().filter(|entry| entry.valid_until.is_some()) {
+        RetiredKeys::<T>::insert(&entry.key, issuer);
+    }
+    IssuerKeys::<T>::insert(issuer, BoundedVec::truncate_from(history));
+    SigningKeys::<T>::insert(key, issuer);
+}
+
+/// A time far enough ahead for certificates to still be in force
+fn later<T: Config>() -> Moment {
+    Pallet::<T>::now().saturating_add(T::ExpiryGranularity::get().saturating_mul(100))
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn issue_cert(m: Linear<11, { T::MaxMetadataLength::get() }>) {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        fill_account::<T>(&recipient);
+        let id = CertificateCount::<T>::get();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            T::Lookup::unlookup(recipient.clone()),
+            cert_type,
+            metadata(m),
+            Some(later::<T>()),
+        );
+
+        assert_last_event::<T>(
+            Event::CertificateIssued { id, owner: recipient, issuer: issuer.clone(), issued_by: issuer, cert_type }
+                .into(),
+        );
+    }
+
+    #[benchmark]
+    fn revoke_cert() {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let id = issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, id, RevocationReason::Fraud, Some(T::Hash::default()));
+
+        assert_eq!(Pallet::<T>::certificate_status(id), Some(CertificateStatus::Revoked));
+    }
+
+    #[benchmark]
+    fn suspend_cert() {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let id = issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, id);
+
+        assert_last_event::<T>(Event::CertificateSuspended { id }.into());
+    }
+
+    #[benchmark]
+    fn reinstate_cert() {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let id = issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+        Certificates::<T>::mutate(id, |cert| {
+            if let Some(cert) = cert {
+                cert.status = CertificateStatus::Suspended;
+            }
+        });
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, id);
+
+        assert_last_event::<T>(Event::CertificateReinstated { id }.into());
+    }
+
+    #[benchmark]
+    fn renew_cert(m: Linear<11, { T::MaxMetadataLength::get() }>) {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        // The renewed certificate sits at the end of a full list
+        fill_account::<T>(&recipient);
+        let id = issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+        let expires_at = later::<T>().saturating_add(T::ExpiryGranularity::get());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, id, Some(metadata(m)), Some(expires_at));
+
+        assert_eq!(Pallet::<T>::certificate_status(id), Some(CertificateStatus::Superseded));
+    }
+
+    #[benchmark]
+    fn register_issuer(t: Linear<0, { T::MaxCertificateTypesPerIssuer::get() }>) {
+        let origin = registrar_origin::<T>();
+        let issuer: T::AccountId = account("issuer", 0, SEED);
+        let types = (0..t)
+            .map(|i| {
+                let type_id = T::CertificateTypeId::from(i);
+                CertificateTypes::<T>::insert(type_id, type_info::<T>());
+                type_id
+            })
+            .collect::<Vec<_>>();
+        let field = vec![b'a'; T::MaxIssuerFieldLength::get() as usize];
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            T::Lookup::unlookup(issuer.clone()),
+            field.clone(),
+            field.clone(),
+            field,
+            types,
+        );
+
+        assert_last_event::<T>(Event::IssuerRegistered { issuer }.into());
+    }
+
+    #[benchmark]
+    fn suspend_issuer() {
+        let origin = registrar_origin::<T>();
+        let issuer: T::AccountId = account("issuer", 0, SEED);
+        setup_issuer::<T>(&issuer);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, T::Lookup::unlookup(issuer.clone()));
+
+        assert_last_event::<T>(Event::IssuerSuspended { issuer }.into());
+    }
+
+    #[benchmark]
+    fn reinstate_issuer() {
+        let origin = registrar_origin::<T>();
+        let issuer: T::AccountId = account("issuer", 0, SEED);
+        setup_issuer::<T>(&issuer);
+        Issuers::<T>::mutate(&issuer, |info| {
+            if let Some(info) = info {
+                info.status = IssuerStatus::Suspended;
+            }
+        });
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, T::Lookup::unlookup(issuer.clone()));
+
+        assert_last_event::<T>(Event::IssuerReinstated { issuer }.into());
+    }
+
+    #[benchmark]
+    fn remove_issuer(
+        t: Linear<0, { T::MaxCertificateTypesPerIssuer::get() }>,
+        d: Linear<0, { T::MaxDelegatesPerIssuer::get() }>,
+    ) {
+        let origin = registrar_origin::<T>();
+        let issuer: T::AccountId = account("issuer", 0, SEED);
+        setup_issuer::<T>(&issuer);
+        Issuers::<T>::mutate(&issuer, |info| {
+            let types = &mut info.as_mut().expect("issuer is registered").certificate_types;
+            for type_id in types.iter().skip(t as usize) {
+                Accreditations::<T>::remove(&issuer, type_id);
+            }
+            types.truncate(t as usize);
+        });
+        fill_delegates::<T>(&issuer, d);
+        setup_key_history::<T>(&issuer, &account("key", 0, SEED));
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, T::Lookup::unlookup(issuer.clone()));
+
+        assert_last_event::<T>(Event::IssuerRemoved { issuer }.into());
+    }
+
+    #[benchmark]
+    fn register_certificate_type(f: Linear<0, { T::MaxRequiredFields::get() }>) {
+        let origin = registrar_origin::<T>();
+        let type_id = T::CertificateTypeId::from(0);
+        let name = vec![b'a'; T::MaxTypeNameLength::get() as usize];
+        let required_fields = (0..f).map(|_| name.clone()).collect::<Vec<_>>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, type_id, name, 1, required_fields);
+
+        assert_last_event::<T>(Event::CertificateTypeRegistered { type_id }.into());
+    }
+
+    #[benchmark]
+    fn remove_certificate_type() {
+        let origin = registrar_origin::<T>();
+        let type_id = T::CertificateTypeId::from(0);
+        CertificateTypes::<T>::insert(type_id, type_info::<T>());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, type_id);
+
+        assert_last_event::<T>(Event::CertificateTypeRemoved { type_id }.into());
+    }
+
+    // Every entry carries maximum-length metadata, so the weight covers any batch of `n`
+    #[benchmark]
+    fn issue_batch(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let certificates = (0..n)
+            .map(|i| {
+                let recipient: T::AccountId = account("recipient", i, SEED);
+                fill_account::<T>(&recipient);
+                (
+                    T::Lookup::unlookup(recipient),
+                    metadata(T::MaxMetadataLength::get()),
+                    Some(later::<T>()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, cert_type, BoundedVec::truncate_from(certificates));
+
+        assert_last_event::<T>(Event::BatchIssued { issuer, cert_type, count: n }.into());
+    }
+
+    #[benchmark]
+    fn prune_certificates(c: Linear<1, { T::MaxCertificatesPerAccount::get() }>) {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let caller: T::AccountId = whitelisted_caller();
+        for _ in 0..c {
+            let id = issue::<T>(&issuer, &caller, cert_type, later::<T>());
+            Certificates::<T>::mutate(id, |cert| {
+                if let Some(cert) = cert {
+                    cert.status = CertificateStatus::Revoked;
+                }
+            });
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert_last_event::<T>(Event::CertificatesPruned { account: caller, count: c }.into());
+    }
+
+    #[benchmark]
+    fn issue_cert_hashed() {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        fill_account::<T>(&recipient);
+        let id = CertificateCount::<T>::get();
+        let locator = vec![b'a'; T::MaxLocatorLength::get() as usize];
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            T::Lookup::unlookup(recipient.clone()),
+            cert_type,
+            T::Hash::default(),
+            Some(locator),
+            Some(later::<T>()),
+        );
+
+        assert_last_event::<T>(
+            Event::CertificateIssued { id, owner: recipient, issuer: issuer.clone(), issued_by: issuer, cert_type }
+                .into(),
+        );
+    }
+
+    #[benchmark]
+    fn set_prerequisites(p: Linear<1, { T::MaxPrerequisites::get() }>) {
+        let origin = registrar_origin::<T>();
+        let prerequisites = (0..p)
+            .map(|i| {
+                let type_id = T::CertificateTypeId::from(i);
+                CertificateTypes::<T>::insert(type_id, type_info::<T>());
+                type_id
+            })
+            .collect::<Vec<_>>();
+        let type_id = T::CertificateTypeId::from(p);
+        CertificateTypes::<T>::insert(type_id, type_info::<T>());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, type_id, prerequisites.clone(), true);
+
+        assert_last_event::<T>(
+            Event::PrerequisitesSet { type_id, prerequisites, enforce_at_issuance: true }.into(),
+        );
+    }
+
+    #[benchmark]
+    fn check_prerequisites(c: Linear<0, { T::MaxCertificatesPerAccount::get() }>) {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        // Certificates in force of an accreditation-required type are the most expensive to check
+        for _ in 0..c {
+            issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+        }
+        let rule = PrerequisiteRule {
+            certificate_types: BoundedVec::truncate_from(vec![cert_type; T::MaxPrerequisites::get() as usize]),
+            enforce_at_issuance: true,
+        };
+        let met;
+
+        #[block]
+        {
+            met = Pallet::<T>::prerequisites_met(&rule, &recipient);
+        }
+
+        assert_eq!(met, c > 0);
+    }
+
+    #[benchmark]
+    fn create_site_profile(r: Linear<0, { T::MaxSiteRequirements::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+        let name = vec![b'a'; T::MaxSiteNameLength::get() as usize];
+        let requirements = site_requirements::<T>(r);
+        let site_id = SiteCount::<T>::get();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), name, requirements);
+
+        assert_last_event::<T>(Event::SiteProfileCreated { site_id, owner: caller }.into());
+    }
+
+    #[benchmark]
+    fn update_site_profile(r: Linear<0, { T::MaxSiteRequirements::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        let site_id = setup_site::<T>(&caller);
+        let name = vec![b'b'; T::MaxSiteNameLength::get() as usize];
+        let requirements = site_requirements::<T>(r);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), site_id, name, requirements);
+
+        assert_last_event::<T>(Event::SiteProfileUpdated { site_id }.into());
+    }
+
+    #[benchmark]
+    fn remove_site_profile() {
+        let caller: T::AccountId = whitelisted_caller();
+        let site_id = setup_site::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), site_id);
+
+        assert_last_event::<T>(Event::SiteProfileRemoved { site_id }.into());
+    }
+
+    #[benchmark]
+    fn add_delegate() {
+        let (origin, issuer) = issuer_origin::<T>();
+        setup_issuer::<T>(&issuer);
+        fill_delegates::<T>(&issuer, T::MaxDelegatesPerIssuer::get().saturating_sub(1));
+        let delegate: T::AccountId = account("delegate", u32::MAX, SEED);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, T::Lookup::unlookup(delegate.clone()));
+
+        assert_last_event::<T>(Event::DelegateAdded { issuer, delegate }.into());
+    }
+
+    #[benchmark]
+    fn remove_delegate() {
+        let (_, issuer) = issuer_origin::<T>();
+        let delegate: T::AccountId = whitelisted_caller();
+        fill_delegates::<T>(&issuer, T::MaxDelegatesPerIssuer::get().saturating_sub(1));
+        Delegates::<T>::insert(&delegate, &issuer);
+        IssuerDelegates::<T>::mutate(&issuer, |delegates| delegates.try_push(delegate.clone()))
+            .expect("one delegate slot is left");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(delegate.clone()), T::Lookup::unlookup(delegate.clone()));
+
+        assert_last_event::<T>(Event::DelegateRemoved { issuer, delegate }.into());
+    }
+
+    #[benchmark]
+    fn rotate_issuer_key() {
+        let (origin, key) = issuer_origin::<T>();
+        let issuer: T::AccountId = account("issuer", 0, SEED);
+        setup_issuer::<T>(&issuer);
+        setup_key_history::<T>(&issuer, &key);
+        let new_key: T::AccountId = account("new_key", 0, SEED);
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            T::Lookup::unlookup(issuer.clone()),
+            T::Lookup::unlookup(new_key.clone()),
+        );
+
+        assert_last_event::<T>(Event::IssuerKeyRotated { issuer, old_key: key, new_key }.into());
+    }
+
+    #[benchmark]
+    fn set_issuance_fee() {
+        let origin = registrar_origin::<T>();
+        let type_id = T::CertificateTypeId::from(0);
+        CertificateTypes::<T>::insert(type_id, type_info::<T>());
+        let fee = T::Currency::minimum_balance();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, type_id, fee);
+
+        assert_last_event::<T>(Event::IssuanceFeeSet { type_id, fee }.into());
+    }
+
+    #[benchmark]
+    fn offer_cert(m: Linear<11, { T::MaxMetadataLength::get() }>) {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        fill_account::<T>(&recipient);
+        let id = CertificateCount::<T>::get();
+        let lapses_at = frame_system::Pallet::<T>::block_number().saturating_add(T::OfferPeriod::get());
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            T::Lookup::unlookup(recipient.clone()),
+            cert_type,
+            metadata(m),
+            Some(later::<T>()),
+        );
+
+        assert_last_event::<T>(
+            Event::CertificateOffered {
+                id,
+                owner: recipient,
+                issuer: issuer.clone(),
+                issued_by: issuer,
+                cert_type,
+                lapses_at,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn accept_cert() {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let caller: T::AccountId = whitelisted_caller();
+        let id = offer::<T>(origin, &caller, cert_type);
+        fill_account::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), id);
+
+        assert_last_event::<T>(Event::CertificateAccepted { id, owner: caller }.into());
+    }
+
+    #[benchmark]
+    fn reject_cert() {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let id = offer::<T>(origin, &recipient, cert_type);
+        // A third party clearing a lapsed offer is checked against the issuer first
+        let lapses_at = PendingOffers::<T>::get(&recipient, id).expect("offer is pending");
+        frame_system::Pallet::<T>::set_block_number(lapses_at);
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id);
+
+        assert_last_event::<T>(Event::CertificateRejected { id, owner: recipient }.into());
+    }
+
+    #[benchmark]
+    fn burn_cert(e: Linear<0, { T::MaxEndorsementsPerCertificate::get() }>) {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let caller: T::AccountId = whitelisted_caller();
+        fill_account::<T>(&caller);
+        let id = issue::<T>(&issuer, &caller, cert_type, later::<T>());
+        endorse::<T>(id, e);
+        // Burning the middle of a renewal chain links both of its neighbours
+        let neighbour: T::AccountId = account("recipient", 0, SEED);
+        let previous = issue::<T>(&issuer, &neighbour, cert_type, later::<T>());
+        let next = issue::<T>(&issuer, &neighbour, cert_type, later::<T>());
+        Certificates::<T>::mutate(previous, |cert| cert.as_mut().map(|cert| cert.superseded_by = Some(id)));
+        Certificates::<T>::mutate(id, |cert| {
+            cert.as_mut().map(|cert| {
+                cert.supersedes = Some(previous);
+                cert.superseded_by = Some(next);
+            })
+        });
+        Certificates::<T>::mutate(next, |cert| cert.as_mut().map(|cert| cert.supersedes = Some(id)));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), id);
+
+        assert_last_event::<T>(Event::CertificateBurned { id, owner: caller }.into());
+        assert_eq!(Certificates::<T>::get(next).and_then(|cert| cert.supersedes), Some(previous));
+        assert!(Tombstones::<T>::contains_key(id));
+    }
+
+    #[benchmark]
+    fn endorse_cert() {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let id = issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+        endorse::<T>(id, T::MaxEndorsementsPerCertificate::get().saturating_sub(1));
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+        let claim_hash = T::Hash::default();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), id, claim_hash);
+
+        assert_last_event::<T>(Event::CertificateEndorsed { id, endorser: caller, claim_hash }.into());
+    }
+
+    #[benchmark]
+    fn revoke_endorsement() {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let caller: T::AccountId = whitelisted_caller();
+        let id = issue::<T>(&issuer, &caller, cert_type, later::<T>());
+        let count = T::MaxEndorsementsPerCertificate::get();
+        endorse::<T>(id, count);
+        // The holder dismissing the newest endorsement also reads the certificate
+        let endorser: T::AccountId = account("endorser", count.saturating_sub(1), SEED);
+        let claim_hash = T::Hash::default();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, T::Lookup::unlookup(endorser.clone()), claim_hash);
+
+        assert_last_event::<T>(Event::EndorsementRevoked { id, endorser, claim_hash }.into());
+    }
+
+    #[benchmark]
+    fn claim_cert(m: Linear<11, { T::MaxMetadataLength::get() }>) {
+        let (public, issuer) = T::BenchmarkHelper::signer();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let caller: T::AccountId = whitelisted_caller();
+        fill_account::<T>(&caller);
+        let voucher = Voucher {
+            issuer: issuer.clone(),
+            recipient: caller.clone(),
+            cert_type,
+            metadata: BoundedVec::truncate_from(metadata(m)),
+            expires_at: Some(later::<T>()),
+            nonce: 0,
+            valid_until: Pallet::<T>::now().saturating_add(T::MaxVoucherValidity::get()),
+        };
+        let signature = T::BenchmarkHelper::sign(&public, &Pallet::<T>::voucher_payload(&voucher));
+        let id = CertificateCount::<T>::get();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), voucher, issuer.clone(), signature);
+
+        assert_last_event::<T>(
+            Event::CertificateClaimed {
+                id,
+                owner: caller,
+                issuer: issuer.clone(),
+                issued_by: issuer,
+                cert_type,
+                nonce: 0,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn register_accreditor() {
+        let origin = registrar_origin::<T>();
+        let accreditor: T::AccountId = account("accreditor", 0, SEED);
+        let name = vec![b'a'; T::MaxIssuerFieldLength::get() as usize];
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, T::Lookup::unlookup(accreditor.clone()), name);
+
+        assert_last_event::<T>(Event::AccreditorRegistered { accreditor }.into());
+    }
+
+    #[benchmark]
+    fn remove_accreditor() {
+        let origin = registrar_origin::<T>();
+        let accreditor: T::AccountId = account("accreditor", 0, SEED);
+        Accreditors::<T>::insert(&accreditor, accreditor_info::<T>());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, T::Lookup::unlookup(accreditor.clone()));
+
+        assert_last_event::<T>(Event::AccreditorRemoved { accreditor }.into());
+    }
+
+    #[benchmark]
+    fn accredit_issuer() {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        // Renewing its own accreditation also checks the current one
+        let accreditor: T::AccountId = account("accreditor", 0, SEED);
+        let expires_at = later::<T>().saturating_add(T::ExpiryGranularity::get());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(accreditor.clone()), T::Lookup::unlookup(issuer.clone()), cert_type, expires_at);
+
+        assert_last_event::<T>(Event::IssuerAccredited { issuer, cert_type, accreditor, expires_at }.into());
+    }
+
+    #[benchmark]
+    fn withdraw_accreditation() {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let accreditor: T::AccountId = account("accreditor", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(accreditor.clone()), T::Lookup::unlookup(issuer.clone()), cert_type);
+
+        assert_last_event::<T>(Event::AccreditationWithdrawn { issuer, cert_type, accreditor }.into());
+    }
+
+    #[benchmark]
+    fn set_accreditation_required() {
+        let origin = registrar_origin::<T>();
+        let type_id = T::CertificateTypeId::from(0);
+        CertificateTypes::<T>::insert(type_id, type_info::<T>());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, type_id, true);
+
+        assert_last_event::<T>(Event::AccreditationRequirementSet { type_id, required: true }.into());
+    }
+
+    #[benchmark]
+    fn force_revoke() {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let id = issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+        let reason = RevocationReason::Fraud;
+        let note_hash = Some(T::Hash::default());
+
+        #[extrinsic_call]
+        _(force_origin::<T>() as T::RuntimeOrigin, id, reason, note_hash);
+
+        let revoked_at = frame_system::Pallet::<T>::block_number();
+        assert_last_event::<T>(Event::CertificateForceRevoked { id, reason, note_hash, revoked_at }.into());
+    }
+
+    #[benchmark]
+    fn force_suspend_issuer() {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        // A removed issuer is only accepted after finding one of its certificates
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        issue::<T>(&issuer, &recipient, cert_type, later::<T>());
+        Issuers::<T>::remove(&issuer);
+        let action = CascadeAction::Revoke(RevocationReason::Fraud);
+
+        #[extrinsic_call]
+        _(force_origin::<T>() as T::RuntimeOrigin, T::Lookup::unlookup(issuer.clone()), action);
+
+        assert_last_event::<T>(Event::IssuerForceSuspended { issuer, action }.into());
+    }
+
+    #[benchmark]
+    fn process_cascade(c: Linear<0, { T::MaxCascadePerBlock::get() }>) {
+        let (origin, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        // Withdrawing a pending offer is the most expensive step of a cascade
+        for i in 0..c {
+            let recipient: T::AccountId = account("recipient", i, SEED);
+            offer::<T>(origin.clone(), &recipient, cert_type);
+        }
+        Cascades::<T>::insert(&issuer, Cascade { action: CascadeAction::Revoke(RevocationReason::Fraud), last: None });
+
+        #[block]
+        {
+            Pallet::<T>::process_cascade();
+        }
+
+        assert!(!Cascades::<T>::contains_key(&issuer));
+        assert_eq!(IssuerCertificates::<T>::iter_key_prefix(&issuer).count(), 0);
+    }
+
+    #[benchmark]
+    fn process_expiries(
+        b: Linear<1, { T::MaxExpiriesPerBlock::get() / 2 }>,
+        e: Linear<0, { T::MaxExpiriesPerBlock::get() / 2 }>,
+    ) {
+        let (_, issuer) = issuer_origin::<T>();
+        let cert_type = setup_issuer::<T>(&issuer);
+        let granularity = T::ExpiryGranularity::get();
+        // `b - 1` empty buckets are carried over before the bucket holding `e` expiries
+        let now = Pallet::<T>::expiry_bucket(Pallet::<T>::now()).saturating_add(granularity * b as u64);
+        for i in 0..e {
+            let recipient: T::AccountId = account("recipient", i, SEED);
+            issue::<T>(&issuer, &recipient, cert_type, now);
+        }
+        ExpiryCursor::<T>::put(now.saturating_sub(granularity * (b - 1) as u64));
+
+        #[block]
+        {
+            Pallet::<T>::process_expiries(now);
+        }
+
+        assert_eq!(ExpiryCursor::<T>::get(), Some(now.saturating_add(granularity)));
+    }
+
+    #[benchmark]
+    fn process_nonce_expiries(
+        b: Linear<1, { T::MaxExpiriesPerBlock::get() / 2 }>,
+        n: Linear<0, { T::MaxExpiriesPerBlock::get() / 2 }>,
+    ) {
+        let (_, issuer) = issuer_origin::<T>();
+        let granularity = T::ExpiryGranularity::get();
+        // `b - 1` empty buckets are carried over before the bucket holding `n` nonces
+        let now = Pallet::<T>::expiry_bucket(Pallet::<T>::now()).saturating_add(granularity * b as u64);
+        for nonce in 0..n as u64 {
+            UsedNonces::<T>::insert(&issuer, nonce, now);
+            NonceExpiries::<T>::insert(now, (&issuer, nonce), ());
+        }
+        NonceCursor::<T>::put(now.saturating_sub(granularity * (b - 1) as u64));
+
+        #[block]
+        {
+            Pallet::<T>::process_nonce_expiries(now);
+        }
+
+        assert_eq!(NonceCursor::<T>::get(), Some(now.saturating_add(granularity)));
+        assert_eq!(UsedNonces::<T>::iter_prefix(&issuer).count(), 0);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
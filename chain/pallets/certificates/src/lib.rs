@@ -5,25 +5,127 @@ pub use pallet::*;
 #[cfg(feature = "std")]
 pub mod rpc;
 
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
         dispatch::DispatchResult,
         pallet_prelude::*,
-        traits::StorageVersion,
+        traits::{Currency, ExistenceRequirement, ReservableCurrency, StorageVersion, UnixTime},
     };
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
-    use sp_runtime::traits::StaticLookup;
+    use sp_runtime::traits::{Hash, IdentifyAccount, SaturatedConversion, Saturating, StaticLookup, Verify, Zero};
     use codec::{Encode, Decode, MaxEncodedLen};
     use scale_info::TypeInfo;
+    use crate::weights::WeightInfo;
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+    
+    pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    
+    /// Milliseconds since the Unix epoch
+    pub type Moment = u64;
+    
+    /// Maximum number of certificates returned by one `get_issuer_certificates` page
+    pub const MAX_ISSUER_PAGE_SIZE: u32 = 1_000;
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    /// Certificate lifecycle status
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub enum CertificateStatus {
+        /// Certificate is in force (subject to expiry)
+        Active,
+        /// Certificate is temporarily suspended, e.g. pending investigation of a safety incident
+        Suspended,
+        /// Certificate is permanently revoked
+        Revoked,
+        /// Certificate has been replaced by a newer certificate
+        Superseded,
+        /// Certificate has been offered and awaits its holder's acceptance
+        Pending,
+        /// Certificate is suspended by the regulator, which alone can reinstate it
+        ForceSuspended,
+    }
+
+    /// Reason a certificate was revoked
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RevocationReason {
+        /// Certificate was issued in error
+        IssuedInError,
+        /// Certificate was obtained fraudulently
+        Fraud,
+        /// Holder failed a re-assessment
+        FailedReassessment,
+        /// Certificate was replaced by another certificate
+        Superseded,
+        /// Holder requested revocation
+        HolderRequest,
+    }
+
+    /// What a regulator does to each of an issuer's certificates when force-suspending it
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum CascadeAction {
+        /// Suspend the certificates in force
+        Suspend,
+        /// Revoke every certificate in force or suspended, for the given reason
+        Revoke(RevocationReason),
+    }
+
+    /// Regulator action still being applied to an issuer's certificates
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Cascade<T: Config> {
+        /// Action applied to each certificate
+        pub action: CascadeAction,
+        /// Last certificate processed, after which the cascade resumes
+        pub last: Option<T::CertificateId>,
+    }
+
+    /// Revocation record kept on a revoked certificate
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct RevocationInfo<T: Config> {
+        /// Revocation reason code
+        pub reason: RevocationReason,
+        /// Hash of an off-chain free-text note explaining the revocation
+        pub note_hash: Option<T::Hash>,
+        /// Certificate revocation time
+        pub revoked_at: T::BlockNumber,
+    }
+
+    /// Certificate metadata, either stored on chain or committed to by hash
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub enum CertificateContent<T: Config> {
+        /// JSON metadata stored on chain
+        Inline(BoundedVec<u8, T::MaxMetadataLength>),
+        /// Hash of a JSON metadata document kept off chain by the issuer and holder
+        Hashed {
+            /// `T::Hashing` hash of the document
+            hash: T::Hash,
+            /// Content-addressed locator of the document (e.g. an IPFS CID), if published
+            locator: Option<BoundedVec<u8, T::MaxLocatorLength>>,
+        },
+    }
+
     /// Certificate metadata structure
     #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
@@ -32,16 +134,262 @@ pub mod pallet {
         pub id: T::CertificateId,
         /// Certificate owner
         pub owner: T::AccountId,
-        /// Certificate issuer
+        /// Certificate issuer (the issuing organization)
         pub issuer: T::AccountId,
-        /// Certificate metadata (stored as JSON string)
-        pub metadata: BoundedVec<u8, T::MaxMetadataLength>,
+        /// Account that signed the issuance: the issuer itself or one of its delegates
+        pub issued_by: T::AccountId,
+        /// Certificate type
+        pub cert_type: T::CertificateTypeId,
+        /// Certificate metadata, inline or as a hash of an off-chain document
+        pub content: CertificateContent<T>,
+        /// Storage deposit held from the issuer until the certificate is pruned
+        pub deposit: BalanceOf<T>,
         /// Certificate issuance time
         pub issued_at: T::BlockNumber,
-        /// Certificate revocation status
-        pub revoked: bool,
-        /// Certificate expiry time (0 if no expiry)
-        pub expires_at: T::BlockNumber,
+        /// Certificate lifecycle status
+        pub status: CertificateStatus,
+        /// Revocation record (set once revoked)
+        pub revocation: Option<RevocationInfo<T>>,
+        /// Certificate expiry time in milliseconds since the Unix epoch (0 if no expiry)
+        pub expires_at: Moment,
+        /// Certificate this one renews, if any
+        pub supersedes: Option<T::CertificateId>,
+        /// Certificate that renewed this one, if any
+        pub superseded_by: Option<T::CertificateId>,
+    }
+
+    /// Third-party attestation recorded against a certificate
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Endorsement<T: Config> {
+        /// Account attesting the claim, e.g. an employer or site supervisor
+        pub endorser: T::AccountId,
+        /// `T::Hashing` hash of the claim document kept off chain
+        pub claim_hash: T::Hash,
+        /// Block the endorsement was recorded in
+        pub endorsed_at: T::BlockNumber,
+        /// Deposit held from the endorser until the endorsement is revoked
+        pub deposit: BalanceOf<T>,
+    }
+
+    /// Certificate an issuer signed off chain for its holder to claim
+    ///
+    /// The issuer signs `Pallet::voucher_payload`, which binds the voucher to the chain's genesis
+    /// block.
+    #[derive(CloneNoBound, Encode, Decode, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Voucher<T: Config> {
+        /// Issuing organization
+        pub issuer: T::AccountId,
+        /// Only account that may claim the certificate
+        pub recipient: T::AccountId,
+        /// Certificate type
+        pub cert_type: T::CertificateTypeId,
+        /// Certificate metadata JSON
+        pub metadata: BoundedVec<u8, T::MaxMetadataLength>,
+        /// Certificate expiry time in milliseconds since the Unix epoch (`None` for the type's default)
+        pub expires_at: Option<Moment>,
+        /// Number the issuer picks once per voucher, so a voucher can be claimed only once
+        pub nonce: u64,
+        /// Time until which the voucher can be claimed, in milliseconds since the Unix epoch
+        pub valid_until: Moment,
+    }
+
+    /// What is kept of a certificate its holder burned
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Tombstone<T: Config> {
+        /// Certificate issuer
+        pub issuer: T::AccountId,
+        /// `T::Hashing` hash of the certificate's metadata document
+        pub content_hash: T::Hash,
+        /// Certificate lifecycle status when it was burned
+        pub status: CertificateStatus,
+        /// Block the certificate was burned in
+        pub burned_at: T::BlockNumber,
+    }
+
+    /// Entry of an issuer's certificate listing
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub enum IssuedCertificate<T: Config> {
+        /// Certificate still stored
+        Stored(Certificate<T>),
+        /// Certificate its holder burned, of which only the tombstone is left
+        Burned { id: T::CertificateId, tombstone: Tombstone<T> },
+    }
+
+    impl<T: Config> IssuedCertificate<T> {
+        /// ID of the certificate
+        pub fn id(&self) -> T::CertificateId {
+            match self {
+                Self::Stored(cert) => cert.id,
+                Self::Burned { id, .. } => *id,
+            }
+        }
+    }
+
+    /// Issuer registration status
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum IssuerStatus {
+        /// Issuer may issue certificates
+        Active,
+        /// Issuer is temporarily barred from issuing certificates
+        Suspended,
+    }
+
+    /// Issuer registry entry
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct IssuerInfo<T: Config> {
+        /// Legal name of the training provider
+        pub legal_name: BoundedVec<u8, T::MaxIssuerFieldLength>,
+        /// Accreditation number assigned to the training provider
+        pub accreditation_number: BoundedVec<u8, T::MaxIssuerFieldLength>,
+        /// Training provider website
+        pub website: BoundedVec<u8, T::MaxIssuerFieldLength>,
+        /// Certificate types the issuer may issue
+        pub certificate_types: BoundedVec<T::CertificateTypeId, T::MaxCertificateTypesPerIssuer>,
+        /// Issuer registration status
+        pub status: IssuerStatus,
+        /// Issuer registration time
+        pub registered_at: T::BlockNumber,
+    }
+
+    /// Signing key an issuer identity used, and when
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct IssuerKey<T: Config> {
+        /// Signing account
+        pub key: T::AccountId,
+        /// First block the key could sign in
+        pub valid_from: T::BlockNumber,
+        /// Block the key was rotated out (`None` for the current key)
+        pub valid_until: Option<T::BlockNumber>,
+    }
+
+    /// Accreditation body registry entry
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct AccreditorInfo<T: Config> {
+        /// Name of the accreditation body
+        pub name: BoundedVec<u8, T::MaxIssuerFieldLength>,
+        /// Accreditor registration time
+        pub registered_at: T::BlockNumber,
+    }
+
+    /// Accreditation of an issuer for a certificate type
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Accreditation<T: Config> {
+        /// Accreditor that granted the accreditation
+        pub accreditor: T::AccountId,
+        /// Time the accreditation was granted, in milliseconds since the Unix epoch
+        pub granted_at: Moment,
+        /// Time the accreditation ends, in milliseconds since the Unix epoch
+        pub expires_at: Moment,
+    }
+
+    /// Certificate type registry entry
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CertificateTypeInfo<T: Config> {
+        /// Canonical display name (e.g. "WHMIS 2015")
+        pub name: BoundedVec<u8, T::MaxTypeNameLength>,
+        /// Validity period in milliseconds applied when no expiry is given at issuance (0 if no expiry)
+        pub default_validity: Moment,
+        /// Top-level fields the certificate metadata JSON object must carry
+        pub required_fields: BoundedVec<BoundedVec<u8, T::MaxTypeNameLength>, T::MaxRequiredFields>,
+    }
+
+    /// Certificate types a holder must also hold for a certificate of another type to be in force
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct PrerequisiteRule<T: Config> {
+        /// Types of which the holder needs a valid certificate each
+        pub certificate_types: BoundedVec<T::CertificateTypeId, T::MaxPrerequisites>,
+        /// Whether issuance is refused while a prerequisite is not met
+        pub enforce_at_issuance: bool,
+    }
+
+    /// Certificate type a site requires its workers to hold
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SiteRequirement<T: Config> {
+        /// Required certificate type
+        pub cert_type: T::CertificateTypeId,
+        /// Validity the certificate must have left, in milliseconds (0 if any valid certificate will do)
+        pub min_remaining_validity: Moment,
+    }
+
+    /// Named set of certificate requirements for working on a site
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SiteProfile<T: Config> {
+        /// Account managing the profile
+        pub owner: T::AccountId,
+        /// Site name
+        pub name: BoundedVec<u8, T::MaxSiteNameLength>,
+        /// Certificates a worker needs for the site
+        pub requirements: BoundedVec<SiteRequirement<T>, T::MaxSiteRequirements>,
+        /// Deposit held from the owner until the profile is removed
+        pub deposit: BalanceOf<T>,
+    }
+
+    /// Result of checking an account against a site profile
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ComplianceReport<CertificateTypeId, CertificateId> {
+        /// Whether the account meets every requirement
+        pub compliant: bool,
+        /// Required types the account holds no effective certificate of
+        pub missing: Vec<CertificateTypeId>,
+        /// Required types whose best certificate has less than the minimum validity left,
+        /// with that certificate
+        pub expiring: Vec<(CertificateTypeId, CertificateId)>,
+    }
+
+    /// Chain of trust behind a certificate, from its issuer up to the accreditor that vouched for it
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub struct TrustChain<AccountId> {
+        /// Issuing organization
+        pub issuer: AccountId,
+        /// Whether the issuer is still registered and active
+        pub issuer_active: bool,
+        /// Accreditor that had accredited the issuer for the certificate's type when it was issued
+        pub accreditor: Option<AccountId>,
+        /// Start and end of that accreditation, in milliseconds since the Unix epoch
+        pub accreditation_period: Option<(Moment, Moment)>,
+        /// Whether that accreditor is still registered
+        pub accreditor_registered: bool,
+        /// Whether certificates of the type are only valid if their issuer was accredited
+        pub accreditation_required: bool,
+    }
+
+    /// Key generation and signing for the `claim_cert` benchmark
+    #[cfg(feature = "runtime-benchmarks")]
+    pub trait BenchmarkHelper<Public, AccountId, Signature> {
+        /// Generate a signing key, returning it with its account
+        fn signer() -> (Public, AccountId);
+        /// Sign a message with a key from `signer`
+        fn sign(signer: &Public, message: &[u8]) -> Signature;
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl BenchmarkHelper<sp_runtime::MultiSigner, sp_runtime::AccountId32, sp_runtime::MultiSignature> for () {
+        fn signer() -> (sp_runtime::MultiSigner, sp_runtime::AccountId32) {
+            let public = sp_io::crypto::sr25519_generate(0.into(), None);
+            let signer = sp_runtime::MultiSigner::Sr25519(public);
+            (signer.clone(), signer.into_account())
+        }
+        
+        fn sign(signer: &sp_runtime::MultiSigner, message: &[u8]) -> sp_runtime::MultiSignature {
+            let public = signer.clone().try_into().expect("benchmark signer is an sr25519 key");
+            sp_runtime::MultiSignature::Sr25519(
+                sp_io::crypto::sr25519_sign(0.into(), &public, message).expect("benchmark key was generated"),
+            )
+        }
     }
 
     #[pallet::config]
@@ -50,14 +398,143 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         
         /// The certificate ID type
-        type CertificateId: Member + Parameter + MaxEncodedLen + Copy + From<u32> + Into<u32>;
+        type CertificateId: Member + Parameter + MaxEncodedLen + Copy + Default + From<u32> + Into<u32>;
+        
+        /// The certificate type ID type
+        type CertificateTypeId: Member + Parameter + MaxEncodedLen + Copy + From<u32>;
+        
+        /// The site profile ID type
+        type SiteId: Member + Parameter + MaxEncodedLen + Copy + Default + From<u32> + Into<u32>;
+        
+        /// The currency in which certificate deposits and issuance fees are paid
+        type Currency: ReservableCurrency<Self::AccountId>;
+        
+        /// Deposit reserved from the issuer for every certificate
+        #[pallet::constant]
+        type CertificateDepositBase: Get<BalanceOf<Self>>;
+        
+        /// Additional deposit reserved per byte of certificate metadata stored on chain
+        #[pallet::constant]
+        type CertificateDepositPerByte: Get<BalanceOf<Self>>;
+        
+        /// Deposit reserved from the endorser for every endorsement
+        #[pallet::constant]
+        type EndorsementDeposit: Get<BalanceOf<Self>>;
+        
+        /// Deposit held from the owner of a site profile for as long as it is stored
+        #[pallet::constant]
+        type SiteProfileDeposit: Get<BalanceOf<Self>>;
+        
+        /// Account receiving certificate type issuance fees
+        #[pallet::constant]
+        type TreasuryAccount: Get<Self::AccountId>;
         
         /// Maximum metadata length
         #[pallet::constant]
         type MaxMetadataLength: Get<u32>;
         
+        /// Maximum length of an off-chain metadata document locator
+        #[pallet::constant]
+        type MaxLocatorLength: Get<u32>;
+        
+        /// Maximum number of certificates listed for a single account
+        #[pallet::constant]
+        type MaxCertificatesPerAccount: Get<u32>;
+        
+        /// Maximum length of an issuer registry field (legal name, accreditation number, website)
+        #[pallet::constant]
+        type MaxIssuerFieldLength: Get<u32>;
+        
+        /// Maximum number of certificate types a single issuer may issue
+        #[pallet::constant]
+        type MaxCertificateTypesPerIssuer: Get<u32>;
+        
+        /// Maximum length of a certificate type name or metadata field name
+        #[pallet::constant]
+        type MaxTypeNameLength: Get<u32>;
+        
+        /// Maximum number of required metadata fields per certificate type
+        #[pallet::constant]
+        type MaxRequiredFields: Get<u32>;
+        
+        /// Maximum number of prerequisite types per certificate type
+        #[pallet::constant]
+        type MaxPrerequisites: Get<u32>;
+        
+        /// Maximum number of expiries (and expiry index buckets) processed per block;
+        /// the remainder is carried over to the following blocks
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+        
+        /// Maximum number of certificates a regulator cascade processes per block; the remainder
+        /// is carried over to the following blocks
+        #[pallet::constant]
+        type MaxCascadePerBlock: Get<u32>;
+        
+        /// Maximum number of certificates issued in a single batch
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+        
+        /// Maximum length of a site profile name
+        #[pallet::constant]
+        type MaxSiteNameLength: Get<u32>;
+        
+        /// Maximum number of requirements per site profile
+        #[pallet::constant]
+        type MaxSiteRequirements: Get<u32>;
+        
+        /// Maximum number of signing keys kept in an issuer's key history; the oldest entries
+        /// are dropped on rotation once it is full
+        #[pallet::constant]
+        type MaxKeyHistory: Get<u32>;
+        
+        /// Maximum number of delegates acting for a single issuer
+        #[pallet::constant]
+        type MaxDelegatesPerIssuer: Get<u32>;
+        
+        /// Maximum number of endorsements recorded against a single certificate
+        #[pallet::constant]
+        type MaxEndorsementsPerCertificate: Get<u32>;
+        
+        /// Number of blocks a holder has to accept a certificate offer before it lapses
+        #[pallet::constant]
+        type OfferPeriod: Get<Self::BlockNumber>;
+        
+        /// Wall-clock time that certificate expiries are compared against
+        type UnixTime: UnixTime;
+        
+        /// Length in milliseconds of the expiry index buckets; a certificate is reported expired
+        /// in the first block once the bucket its expiry falls in has ended
+        #[pallet::constant]
+        type ExpiryGranularity: Get<Moment>;
+        
+        /// Longest time in milliseconds a voucher may stay claimable, which bounds how long its
+        /// nonce is kept once claimed
+        #[pallet::constant]
+        type MaxVoucherValidity: Get<Moment>;
+        
         /// The origin which may issue certificates
-        type IssuerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        type IssuerOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+        
+        /// The origin which may manage the issuer and certificate type registries (root or governance)
+        type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        
+        /// The origin which may override issuers, revoking their certificates or suspending them
+        /// together with all their certificates (a regulator)
+        type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        
+        /// Signature issuers sign certificate vouchers with off chain
+        type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+        
+        /// Public key of an off-chain signature, identifying the signing account
+        type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+        
+        /// Key generation and signing for benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::OffchainPublic, Self::AccountId, Self::OffchainSignature>;
+        
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::storage]
@@ -80,131 +557,2326 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         T::AccountId,
-        BoundedVec<T::CertificateId, T::MaxMetadataLength>,
+        BoundedVec<T::CertificateId, T::MaxCertificatesPerAccount>,
         ValueQuery,
     >;
 
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
-        /// A certificate was issued
-        CertificateIssued {
-            id: T::CertificateId,
-            owner: T::AccountId,
-            issuer: T::AccountId,
-        },
-        /// A certificate was revoked
-        CertificateRevoked {
-            id: T::CertificateId,
-        },
-    }
+    /// Every certificate issued by an issuer, whatever its current status
+    #[pallet::storage]
+    pub type IssuerCertificates<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::CertificateId,
+        (),
+        OptionQuery,
+    >;
 
-    #[pallet::error]
-    pub enum Error<T> {
-        /// Certificate already exists
-        CertificateAlreadyExists,
-        /// Certificate does not exist
-        CertificateNotFound,
-        /// Certificate is already revoked
-        CertificateAlreadyRevoked,
-        /// Certificate does not belong to the account
-        NotCertificateOwner,
-        /// Certificate metadata too long
-        MetadataTooLong,
-        /// Account certificates list is full
-        TooManyCertificates,
-    }
+    #[pallet::storage]
+    #[pallet::getter(fn issuers)]
+    pub type Issuers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        IssuerInfo<T>,
+        OptionQuery,
+    >;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Issue a new certificate to an account
-        #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
-        pub fn issue_cert(
-            origin: OriginFor<T>,
-            recipient: <T::Lookup as StaticLookup>::Source,
-            metadata: Vec<u8>,
-            expires_at: T::BlockNumber,
-        ) -> DispatchResult {
-            let issuer = T::IssuerOrigin::ensure_origin(origin)?;
-            let recipient = T::Lookup::lookup(recipient)?;
-            
-            // Validate metadata length
-            let bounded_metadata = BoundedVec::<u8, T::MaxMetadataLength>::try_from(metadata)
-                .map_err(|_| Error::<T>::MetadataTooLong)?;
-            
-            // Generate a new certificate ID
-            let id = Self::next_certificate_id()?;
-            
-            // Create a new certificate
-            let cert = Certificate {
-                id,
-                owner: recipient.clone(),
-                issuer: issuer.clone(),
-                metadata: bounded_metadata,
-                issued_at: <frame_system::Pallet<T>>::block_number(),
-                revoked: false,
-                expires_at,
-            };
-            
-            // Store the certificate
-            <Certificates<T>>::insert(id, cert);
-            
-            // Update account certificates
-            <AccountCertificates<T>>::try_mutate(&recipient, |certs| {
-                certs.try_push(id).map_err(|_| Error::<T>::TooManyCertificates)
-            })?;
-            
-            // Emit event
-            Self::deposit_event(Event::CertificateIssued {
-                id,
-                owner: recipient,
-                issuer,
-            });
-            
-            Ok(())
-        }
-        
-        /// Revoke a certificate
-        #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
-        pub fn revoke_cert(
-            origin: OriginFor<T>,
-            cert_id: T::CertificateId,
-        ) -> DispatchResult {
-            let issuer = T::IssuerOrigin::ensure_origin(origin)?;
-            
-            // Ensure certificate exists
-            <Certificates<T>>::try_mutate(cert_id, |cert_opt| {
-                let cert = cert_opt.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
-                
-                // Ensure certificate is not already revoked
-                ensure!(!cert.revoked, Error::<T>::CertificateAlreadyRevoked);
-                
-                // Ensure the caller is the issuer
-                ensure!(cert.issuer == issuer, Error::<T>::NotCertificateOwner);
-                
-                // Revoke the certificate
-                cert.revoked = true;
-                
-                // Emit event
-                Self::deposit_event(Event::CertificateRevoked { id: cert_id });
-                
-                Ok(())
-            })
-        }
-    }
+    /// Signing keys of issuers that have rotated their key, by issuer identity, oldest first
+    ///
+    /// Issuers that never rotated sign with their identity account and have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn issuer_keys)]
+    pub type IssuerKeys<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<IssuerKey<T>, T::MaxKeyHistory>,
+        ValueQuery,
+    >;
 
-    impl<T: Config> Pallet<T> {
-        /// Get the next certificate ID
-        fn next_certificate_id() -> Result<T::CertificateId, DispatchError> {
+    /// Issuer identity of each current signing key listed in `IssuerKeys`
+    #[pallet::storage]
+    #[pallet::getter(fn signing_keys)]
+    pub type SigningKeys<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    /// Issuer identity of each signing key rotated out, including identity accounts that
+    /// signed before their first rotation
+    ///
+    /// Kept after the entry has dropped out of `IssuerKeys`, so a retired key can never act or
+    /// be taken into use again.
+    #[pallet::storage]
+    #[pallet::getter(fn retired_keys)]
+    pub type RetiredKeys<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    /// Fee paid to the treasury for each certificate issued of a type
+    #[pallet::storage]
+    #[pallet::getter(fn issuance_fees)]
+    pub type IssuanceFees<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CertificateTypeId,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Issuer each delegate account acts on behalf of
+    #[pallet::storage]
+    #[pallet::getter(fn delegates)]
+    pub type Delegates<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    /// Delegate accounts of each issuer, listed in `Delegates`
+    #[pallet::storage]
+    #[pallet::getter(fn issuer_delegates)]
+    pub type IssuerDelegates<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<T::AccountId, T::MaxDelegatesPerIssuer>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn certificate_types)]
+    pub type CertificateTypes<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CertificateTypeId,
+        CertificateTypeInfo<T>,
+        OptionQuery,
+    >;
+
+    /// Prerequisite rules of certificate types
+    #[pallet::storage]
+    #[pallet::getter(fn prerequisites)]
+    pub type Prerequisites<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CertificateTypeId,
+        PrerequisiteRule<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn site_profiles)]
+    pub type SiteProfiles<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::SiteId,
+        SiteProfile<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn site_count)]
+    pub type SiteCount<T: Config> = StorageValue<_, T::SiteId, ValueQuery>;
+
+    /// Certificates in force indexed by the end of the expiry bucket their expiry falls in
+    #[pallet::storage]
+    pub type ExpiryIndex<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        Moment,
+        Blake2_128Concat,
+        T::CertificateId,
+        (),
+        OptionQuery,
+    >;
+
+    /// End of the earliest expiry bucket whose expiries have not all been processed yet
+    #[pallet::storage]
+    pub type ExpiryCursor<T: Config> = StorageValue<_, Moment, OptionQuery>;
+
+    /// Tombstones of certificates burned by their holders
+    #[pallet::storage]
+    #[pallet::getter(fn tombstones)]
+    pub type Tombstones<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CertificateId,
+        Tombstone<T>,
+        OptionQuery,
+    >;
+
+    /// Registered accreditation bodies
+    #[pallet::storage]
+    #[pallet::getter(fn accreditors)]
+    pub type Accreditors<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        AccreditorInfo<T>,
+        OptionQuery,
+    >;
+
+    /// Current accreditation of each issuer for each certificate type
+    #[pallet::storage]
+    #[pallet::getter(fn accreditations)]
+    pub type Accreditations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::CertificateTypeId,
+        Accreditation<T>,
+        OptionQuery,
+    >;
+
+    /// Accreditation its issuer held for a certificate's type when the certificate was issued
+    #[pallet::storage]
+    #[pallet::getter(fn certificate_accreditations)]
+    pub type CertificateAccreditations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CertificateId,
+        Accreditation<T>,
+        OptionQuery,
+    >;
+
+    /// Certificate types whose certificates are only valid if their issuer was accredited
+    /// for the type when issuing them
+    #[pallet::storage]
+    pub type AccreditationRequired<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CertificateTypeId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Endorsements recorded against each certificate, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn endorsements)]
+    pub type Endorsements<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CertificateId,
+        BoundedVec<Endorsement<T>, T::MaxEndorsementsPerCertificate>,
+        ValueQuery,
+    >;
+
+    /// Regulator actions still being applied to the certificates of force-suspended issuers
+    #[pallet::storage]
+    #[pallet::getter(fn cascades)]
+    pub type Cascades<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Cascade<T>,
+        OptionQuery,
+    >;
+
+    /// Voucher nonces each issuer has had claimed, with the time until which the voucher was valid
+    #[pallet::storage]
+    pub type UsedNonces<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u64,
+        Moment,
+        OptionQuery,
+    >;
+    
+    /// Claimed voucher nonces by the expiry bucket their voucher's validity ends in
+    #[pallet::storage]
+    pub type NonceExpiries<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        Moment,
+        Blake2_128Concat,
+        (T::AccountId, u64),
+        (),
+        OptionQuery,
+    >;
+    
+    /// End of the earliest nonce expiry bucket whose nonces have not all been pruned yet
+    #[pallet::storage]
+    pub type NonceCursor<T: Config> = StorageValue<_, Moment, OptionQuery>;
+
+    /// Pending certificate offers by holder, with the block at which each offer lapses
+    #[pallet::storage]
+    pub type PendingOffers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::CertificateId,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A certificate was issued
+        CertificateIssued {
+            id: T::CertificateId,
+            owner: T::AccountId,
+            issuer: T::AccountId,
+            issued_by: T::AccountId,
+            cert_type: T::CertificateTypeId,
+        },
+        /// A certificate was revoked
+        CertificateRevoked {
+            id: T::CertificateId,
+            reason: RevocationReason,
+            note_hash: Option<T::Hash>,
+            revoked_at: T::BlockNumber,
+        },
+        /// A certificate was suspended
+        CertificateSuspended {
+            id: T::CertificateId,
+        },
+        /// A suspended certificate was reinstated
+        CertificateReinstated {
+            id: T::CertificateId,
+        },
+        /// A certificate was renewed by a successor certificate
+        CertificateRenewed {
+            id: T::CertificateId,
+            supersedes: T::CertificateId,
+            expires_at: Moment,
+        },
+        /// A batch of certificates was issued
+        BatchIssued {
+            issuer: T::AccountId,
+            cert_type: T::CertificateTypeId,
+            count: u32,
+        },
+        /// Revoked and superseded certificates were pruned from an account's list
+        CertificatesPruned {
+            account: T::AccountId,
+            count: u32,
+        },
+        /// A certificate reached its expiry time
+        CertificateExpired {
+            id: T::CertificateId,
+            owner: T::AccountId,
+        },
+        /// An issuer was registered
+        IssuerRegistered {
+            issuer: T::AccountId,
+        },
+        /// An issuer was suspended
+        IssuerSuspended {
+            issuer: T::AccountId,
+        },
+        /// A suspended issuer was reinstated
+        IssuerReinstated {
+            issuer: T::AccountId,
+        },
+        /// An issuer was removed from the registry
+        IssuerRemoved {
+            issuer: T::AccountId,
+        },
+        /// A certificate type was registered
+        CertificateTypeRegistered {
+            type_id: T::CertificateTypeId,
+        },
+        /// A certificate type was removed from the registry
+        CertificateTypeRemoved {
+            type_id: T::CertificateTypeId,
+        },
+        /// The prerequisite rule of a certificate type was set or, if empty, removed
+        PrerequisitesSet {
+            type_id: T::CertificateTypeId,
+            prerequisites: Vec<T::CertificateTypeId>,
+            enforce_at_issuance: bool,
+        },
+        /// A site profile was created
+        SiteProfileCreated {
+            site_id: T::SiteId,
+            owner: T::AccountId,
+        },
+        /// A site profile's name or requirements were changed
+        SiteProfileUpdated {
+            site_id: T::SiteId,
+        },
+        /// A site profile was removed
+        SiteProfileRemoved {
+            site_id: T::SiteId,
+        },
+        /// An account was authorized to issue and manage certificates on an issuer's behalf
+        DelegateAdded {
+            issuer: T::AccountId,
+            delegate: T::AccountId,
+        },
+        /// A delegate's authorization was withdrawn
+        DelegateRemoved {
+            issuer: T::AccountId,
+            delegate: T::AccountId,
+        },
+        /// The issuance fee of a certificate type was set or, if zero, removed
+        IssuanceFeeSet {
+            type_id: T::CertificateTypeId,
+            fee: BalanceOf<T>,
+        },
+        /// An issuer's signing key was replaced
+        IssuerKeyRotated {
+            issuer: T::AccountId,
+            old_key: T::AccountId,
+            new_key: T::AccountId,
+        },
+        /// A certificate was offered to an account and awaits its acceptance
+        CertificateOffered {
+            id: T::CertificateId,
+            owner: T::AccountId,
+            issuer: T::AccountId,
+            issued_by: T::AccountId,
+            cert_type: T::CertificateTypeId,
+            lapses_at: T::BlockNumber,
+        },
+        /// A certificate offer was accepted by its holder
+        CertificateAccepted {
+            id: T::CertificateId,
+            owner: T::AccountId,
+        },
+        /// A certificate offer was rejected, withdrawn or cleared after lapsing
+        CertificateRejected {
+            id: T::CertificateId,
+            owner: T::AccountId,
+        },
+        /// A certificate was burned by its holder, leaving only a tombstone
+        CertificateBurned {
+            id: T::CertificateId,
+            owner: T::AccountId,
+        },
+        /// An accreditation body was registered
+        AccreditorRegistered {
+            accreditor: T::AccountId,
+        },
+        /// An accreditation body was removed from the registry
+        AccreditorRemoved {
+            accreditor: T::AccountId,
+        },
+        /// An issuer was accredited for a certificate type
+        IssuerAccredited {
+            issuer: T::AccountId,
+            cert_type: T::CertificateTypeId,
+            accreditor: T::AccountId,
+            expires_at: Moment,
+        },
+        /// An issuer's accreditation for a certificate type was withdrawn
+        AccreditationWithdrawn {
+            issuer: T::AccountId,
+            cert_type: T::CertificateTypeId,
+            accreditor: T::AccountId,
+        },
+        /// Whether certificates of a type need an accredited issuer to be valid was set
+        AccreditationRequirementSet {
+            type_id: T::CertificateTypeId,
+            required: bool,
+        },
+        /// A certificate was revoked by the regulator
+        CertificateForceRevoked {
+            id: T::CertificateId,
+            reason: RevocationReason,
+            note_hash: Option<T::Hash>,
+            revoked_at: T::BlockNumber,
+        },
+        /// A certificate was suspended by the regulator
+        CertificateForceSuspended {
+            id: T::CertificateId,
+        },
+        /// An issuer was suspended by the regulator, which applies `action` to its certificates
+        IssuerForceSuspended {
+            issuer: T::AccountId,
+            action: CascadeAction,
+        },
+        /// The regulator action on an issuer's certificates was applied to all of them
+        CascadeCompleted {
+            issuer: T::AccountId,
+        },
+        /// A claim about a certificate's holder was endorsed
+        CertificateEndorsed {
+            id: T::CertificateId,
+            endorser: T::AccountId,
+            claim_hash: T::Hash,
+        },
+        /// An endorsement was revoked by its endorser or dismissed by the holder
+        EndorsementRevoked {
+            id: T::CertificateId,
+            endorser: T::AccountId,
+            claim_hash: T::Hash,
+        },
+        /// A certificate was claimed by its holder with a voucher signed by the issuer
+        CertificateClaimed {
+            id: T::CertificateId,
+            owner: T::AccountId,
+            issuer: T::AccountId,
+            issued_by: T::AccountId,
+            cert_type: T::CertificateTypeId,
+            nonce: u64,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Certificate already exists
+        CertificateAlreadyExists,
+        /// Certificate does not exist
+        CertificateNotFound,
+        /// Certificate is already revoked
+        CertificateAlreadyRevoked,
+        /// Certificate is not active
+        CertificateNotActive,
+        /// Certificate is not suspended
+        CertificateNotSuspended,
+        /// Certificate does not belong to the account
+        NotCertificateOwner,
+        /// Certificate metadata too long
+        MetadataTooLong,
+        /// Off-chain metadata document locator too long
+        LocatorTooLong,
+        /// Account certificates list is full
+        TooManyCertificates,
+        /// Issuer is not registered
+        IssuerNotRegistered,
+        /// Issuer is already registered
+        IssuerAlreadyRegistered,
+        /// Issuer is suspended
+        IssuerSuspended,
+        /// Issuer is not suspended
+        IssuerNotSuspended,
+        /// Issuer registry field too long
+        IssuerFieldTooLong,
+        /// Issuer certificate types list is full
+        TooManyCertificateTypes,
+        /// Certificate type is already registered
+        CertificateTypeAlreadyExists,
+        /// Certificate type is not registered
+        CertificateTypeNotFound,
+        /// Issuer may not issue this certificate type
+        CertificateTypeNotAllowed,
+        /// Certificate type name or field name too long, or not valid UTF-8
+        InvalidTypeName,
+        /// Certificate type required fields list is full
+        TooManyRequiredFields,
+        /// Certificate metadata is not a JSON object
+        InvalidMetadata,
+        /// Certificate metadata is missing a field required by its type
+        MissingMetadataField,
+        /// Certificate batch is empty
+        EmptyBatch,
+        /// Account has no revoked or superseded certificates to prune
+        NothingToPrune,
+        /// Certificate type prerequisites list is full
+        TooManyPrerequisites,
+        /// Certificate type cannot be its own prerequisite
+        InvalidPrerequisite,
+        /// Recipient does not hold a valid certificate of every prerequisite type
+        PrerequisiteNotMet,
+        /// Site profile does not exist
+        SiteProfileNotFound,
+        /// Site profile is managed by another account
+        NotSiteOwner,
+        /// Site profile name too long
+        SiteNameTooLong,
+        /// Site profile requirements list is full
+        TooManySiteRequirements,
+        /// No site profile IDs are left
+        TooManySiteProfiles,
+        /// Account is already a delegate of an issuer
+        AlreadyDelegate,
+        /// Account is not a delegate of the issuer
+        NotDelegate,
+        /// A registered issuer cannot be a delegate
+        DelegateIsIssuer,
+        /// Signing key was rotated out and can no longer act for its issuer
+        IssuerKeyRetired,
+        /// Account is a delegate, or is or was the key of an issuer
+        KeyInUse,
+        /// Certificate is not a pending offer
+        CertificateNotPending,
+        /// Certificate is a pending offer; withdraw it with `reject_cert`
+        CertificatePending,
+        /// Certificate offer has lapsed and can no longer be accepted
+        OfferLapsed,
+        /// Accreditor is not registered
+        AccreditorNotRegistered,
+        /// Accreditor is already registered
+        AccreditorAlreadyRegistered,
+        /// Issuer holds no accreditation for the certificate type
+        AccreditationNotFound,
+        /// Accreditation was granted by another accreditor
+        NotAccreditor,
+        /// Issuer holds an accreditation for the certificate type from another accreditor
+        AccreditedByAnother,
+        /// Accreditation would end before it starts
+        InvalidAccreditationExpiry,
+        /// Holders cannot endorse their own certificates
+        SelfEndorsement,
+        /// Endorser has already endorsed this claim on the certificate
+        AlreadyEndorsed,
+        /// Certificate endorsements list is full
+        TooManyEndorsements,
+        /// Endorsement does not exist
+        EndorsementNotFound,
+        /// Voucher can no longer be claimed
+        VoucherExpired,
+        /// Voucher nonce has already been claimed
+        VoucherAlreadyUsed,
+        /// Voucher is made out to another account
+        NotVoucherRecipient,
+        /// Voucher signature does not match the voucher and signer
+        InvalidVoucherSignature,
+        /// Voucher signer does not sign for the voucher's issuer
+        SignerNotIssuer,
+        /// Issuer already has the maximum number of delegates
+        TooManyDelegates,
+        /// Certificate was suspended by the regulator and only the regulator can reinstate it
+        SuspendedByRegulator,
+        /// Voucher stays claimable for longer than `MaxVoucherValidity`
+        VoucherValidityTooLong,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            let now = Self::now();
+            Self::process_expiries(now)
+                .saturating_add(Self::process_nonce_expiries(now))
+                .saturating_add(Self::process_cascade())
+        }
+        
+        fn integrity_test() {
+            assert!(T::MaxCertificatesPerAccount::get() > 0, "MaxCertificatesPerAccount must be greater than zero");
+            assert!(T::MaxExpiriesPerBlock::get() > 0, "MaxExpiriesPerBlock must be greater than zero");
+            assert!(T::ExpiryGranularity::get() > 0, "ExpiryGranularity must be greater than zero");
+            assert!(T::MaxVoucherValidity::get() > 0, "MaxVoucherValidity must be greater than zero");
+            assert!(T::MaxCascadePerBlock::get() > 0, "MaxCascadePerBlock must be greater than zero");
+            assert!(T::MaxBatchSize::get() > 0, "MaxBatchSize must be greater than zero");
+            assert!(T::MaxMetadataLength::get() >= 2, "MaxMetadataLength must fit an empty JSON object");
+            assert!(T::MaxKeyHistory::get() >= 2, "MaxKeyHistory must fit the retired and the new key");
+        }
+        
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+            Self::do_try_state()
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Issue a new certificate of a registered type to an account
+        ///
+        /// A delegate issues on behalf of its issuer; both are recorded on the certificate.
+        /// `expires_at` is in milliseconds since the Unix epoch; if it is `None` the type's default
+        /// validity period is applied.
+        #[pallet::call_index(0)]
+        #[pallet::weight(
+            T::WeightInfo::issue_cert(metadata.len() as u32).saturating_add(Self::prerequisite_check_weight())
+        )]
+        pub fn issue_cert(
+            origin: OriginFor<T>,
+            recipient: <T::Lookup as StaticLookup>::Source,
+            cert_type: T::CertificateTypeId,
+            metadata: Vec<u8>,
+            expires_at: Option<Moment>,
+        ) -> DispatchResult {
+            let signer = T::IssuerOrigin::ensure_origin(origin)?;
+            let issuer = Self::acting_issuer(&signer)?;
+            let recipient = T::Lookup::lookup(recipient)?;
+            
+            let content = Self::inline_content(metadata)?;
+            let id = Self::do_issue(&issuer, &signer, &recipient, cert_type, content, expires_at, None)?;
+            
+            // Emit event
+            Self::deposit_event(Event::CertificateIssued {
+                id,
+                owner: recipient,
+                issuer,
+                issued_by: signer,
+                cert_type,
+            });
+            
+            Ok(())
+        }
+        
+        /// Revoke a certificate, recording why and when
+        ///
+        /// `note_hash` commits to an optional free-text note kept off-chain.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::revoke_cert())]
+        pub fn revoke_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+            reason: RevocationReason,
+            note_hash: Option<T::Hash>,
+        ) -> DispatchResult {
+            let issuer = Self::acting_issuer(&T::IssuerOrigin::ensure_origin(origin)?)?;
+            
+            // Ensure certificate exists
+            <Certificates<T>>::try_mutate(cert_id, |cert_opt| {
+                let cert = cert_opt.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+                
+                // Ensure certificate is not already revoked and was accepted by its holder
+                ensure!(cert.status != CertificateStatus::Revoked, Error::<T>::CertificateAlreadyRevoked);
+                ensure!(cert.status != CertificateStatus::Pending, Error::<T>::CertificatePending);
+                
+                // Ensure the caller is the issuer or one of its delegates
+                ensure!(cert.issuer == issuer, Error::<T>::NotCertificateOwner);
+                
+                // Revoke the certificate
+                let revoked_at = Self::revoke(cert, reason, note_hash);
+                
+                // Emit event
+                Self::deposit_event(Event::CertificateRevoked {
+                    id: cert_id,
+                    reason,
+                    note_hash,
+                    revoked_at,
+                });
+                
+                Ok(())
+            })
+        }
+        
+        /// Temporarily suspend an active certificate
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::suspend_cert())]
+        pub fn suspend_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+        ) -> DispatchResult {
+            let issuer = Self::acting_issuer(&T::IssuerOrigin::ensure_origin(origin)?)?;
+            
+            <Certificates<T>>::try_mutate(cert_id, |cert_opt| {
+                let cert = cert_opt.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+                
+                // Ensure the caller is the issuer or one of its delegates
+                ensure!(cert.issuer == issuer, Error::<T>::NotCertificateOwner);
+                
+                // Only active certificates can be suspended
+                ensure!(cert.status == CertificateStatus::Active, Error::<T>::CertificateNotActive);
+                
+                cert.status = CertificateStatus::Suspended;
+                
+                Self::deposit_event(Event::CertificateSuspended { id: cert_id });
+                
+                Ok(())
+            })
+        }
+        
+        /// Reinstate a suspended certificate
+        ///
+        /// Called by the certificate's issuer, or by the regulator, which alone can reinstate
+        /// certificates it suspended.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::reinstate_cert())]
+        pub fn reinstate_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+        ) -> DispatchResult {
+            let issuer = if T::ForceOrigin::try_origin(origin.clone()).is_ok() {
+                None
+            } else {
+                let issuer = Self::acting_issuer(&T::IssuerOrigin::ensure_origin(origin)?)?;
+                // Only a registered, active issuer may put a certificate back in force
+                Self::ensure_active_issuer(&issuer)?;
+                Some(issuer)
+            };
+            
+            <Certificates<T>>::try_mutate(cert_id, |cert_opt| {
+                let cert = cert_opt.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+                
+                ensure!(
+                    matches!(cert.status, CertificateStatus::Suspended | CertificateStatus::ForceSuspended),
+                    Error::<T>::CertificateNotSuspended
+                );
+                if let Some(issuer) = issuer {
+                    // Ensure the caller is the issuer or one of its delegates
+                    ensure!(cert.issuer == issuer, Error::<T>::NotCertificateOwner);
+                    ensure!(cert.status == CertificateStatus::Suspended, Error::<T>::SuspendedByRegulator);
+                }
+                
+                cert.status = CertificateStatus::Active;
+                
+                Self::deposit_event(Event::CertificateReinstated { id: cert_id });
+                
+                Ok(())
+            })
+        }
+        
+        /// Renew an active certificate with a linked successor
+        ///
+        /// The successor takes the original's place in the holder's certificate list and the
+        /// original is marked `Superseded`. If `metadata` is `None` the original content is kept;
+        /// if `expires_at` is `None` the type's default validity period is applied.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::renew_cert(
+            metadata.as_ref().map_or(T::MaxMetadataLength::get(), |metadata| metadata.len() as u32)
+        ).saturating_add(Self::prerequisite_check_weight()))]
+        pub fn renew_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+            metadata: Option<Vec<u8>>,
+            expires_at: Option<Moment>,
+        ) -> DispatchResult {
+            let signer = T::IssuerOrigin::ensure_origin(origin)?;
+            let issuer = Self::acting_issuer(&signer)?;
+            
+            let cert = <Certificates<T>>::get(cert_id).ok_or(Error::<T>::CertificateNotFound)?;
+            
+            // Ensure the caller is the issuer or one of its delegates
+            ensure!(cert.issuer == issuer, Error::<T>::NotCertificateOwner);
+            
+            // Only certificates in force (possibly expired) can be renewed
+            ensure!(cert.status == CertificateStatus::Active, Error::<T>::CertificateNotActive);
+            
+            let content = match metadata {
+                Some(metadata) => Self::inline_content(metadata)?,
+                None => cert.content,
+            };
+            let id = Self::do_issue(&issuer, &signer, &cert.owner, cert.cert_type, content, expires_at, Some(cert_id))?;
+            
+            // The original has left the holder's list, so its deposit is returned as on pruning
+            <Certificates<T>>::mutate(cert_id, |cert_opt| {
+                if let Some(cert) = cert_opt {
+                    <ExpiryIndex<T>>::remove(Self::expiry_bucket(cert.expires_at), cert_id);
+                    cert.status = CertificateStatus::Superseded;
+                    cert.superseded_by = Some(id);
+                    T::Currency::unreserve(&cert.issuer, sp_std::mem::take(&mut cert.deposit));
+                }
+            });
+            
+            let expires_at = <Certificates<T>>::get(id).map(|cert| cert.expires_at).unwrap_or_default();
+            
+            Self::deposit_event(Event::CertificateRenewed {
+                id,
+                supersedes: cert_id,
+                expires_at,
+            });
+            
+            Ok(())
+        }
+        
+        /// Register a training provider as a certificate issuer
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::register_issuer(certificate_types.len() as u32))]
+        pub fn register_issuer(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+            legal_name: Vec<u8>,
+            accreditation_number: Vec<u8>,
+            website: Vec<u8>,
+            certificate_types: Vec<T::CertificateTypeId>,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            
+            ensure!(!<Issuers<T>>::contains_key(&issuer), Error::<T>::IssuerAlreadyRegistered);
+            ensure!(!<Delegates<T>>::contains_key(&issuer), Error::<T>::AlreadyDelegate);
+            ensure!(
+                !<SigningKeys<T>>::contains_key(&issuer) && !<RetiredKeys<T>>::contains_key(&issuer),
+                Error::<T>::KeyInUse
+            );
+            for type_id in certificate_types.iter() {
+                ensure!(<CertificateTypes<T>>::contains_key(type_id), Error::<T>::CertificateTypeNotFound);
+            }
+            
+            let info = IssuerInfo {
+                legal_name: Self::bounded_field(legal_name)?,
+                accreditation_number: Self::bounded_field(accreditation_number)?,
+                website: Self::bounded_field(website)?,
+                certificate_types: BoundedVec::try_from(certificate_types)
+                    .map_err(|_| Error::<T>::TooManyCertificateTypes)?,
+                status: IssuerStatus::Active,
+                registered_at: <frame_system::Pallet<T>>::block_number(),
+            };
+            
+            <Issuers<T>>::insert(&issuer, info);
+            
+            Self::deposit_event(Event::IssuerRegistered { issuer });
+            
+            Ok(())
+        }
+        
+        /// Suspend a registered issuer, preventing further issuance
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::suspend_issuer())]
+        pub fn suspend_issuer(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            
+            <Issuers<T>>::try_mutate(&issuer, |info_opt| {
+                let info = info_opt.as_mut().ok_or(Error::<T>::IssuerNotRegistered)?;
+                
+                ensure!(info.status == IssuerStatus::Active, Error::<T>::IssuerSuspended);
+                
+                info.status = IssuerStatus::Suspended;
+                
+                Ok::<(), DispatchError>(())
+            })?;
+            
+            Self::deposit_event(Event::IssuerSuspended { issuer });
+            
+            Ok(())
+        }
+        
+        /// Reinstate a suspended issuer
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::reinstate_issuer())]
+        pub fn reinstate_issuer(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            
+            <Issuers<T>>::try_mutate(&issuer, |info_opt| {
+                let info = info_opt.as_mut().ok_or(Error::<T>::IssuerNotRegistered)?;
+                
+                ensure!(info.status == IssuerStatus::Suspended, Error::<T>::IssuerNotSuspended);
+                
+                info.status = IssuerStatus::Active;
+                
+                Ok::<(), DispatchError>(())
+            })?;
+            
+            Self::deposit_event(Event::IssuerReinstated { issuer });
+            
+            Ok(())
+        }
+        
+        /// Remove an issuer from the registry
+        ///
+        /// Certificates already issued are kept; the issuer can no longer issue new ones. Its
+        /// delegates and accreditations are cleared and its signing keys stay retired, so the
+        /// identity account can be registered afresh.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::remove_issuer(
+            T::MaxCertificateTypesPerIssuer::get(),
+            T::MaxDelegatesPerIssuer::get(),
+        ))]
+        pub fn remove_issuer(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            
+            let info = <Issuers<T>>::take(&issuer).ok_or(Error::<T>::IssuerNotRegistered)?;
+            
+            // Accreditations are only granted for the types the issuer may issue
+            for cert_type in info.certificate_types {
+                <Accreditations<T>>::remove(&issuer, cert_type);
+            }
+            for delegate in <IssuerDelegates<T>>::take(&issuer) {
+                <Delegates<T>>::remove(&delegate);
+            }
+            if let Some(current) = <IssuerKeys<T>>::take(&issuer).last() {
+                <SigningKeys<T>>::remove(&current.key);
+                <RetiredKeys<T>>::insert(&current.key, &issuer);
+            }
+            <RetiredKeys<T>>::remove(&issuer);
+            
+            Self::deposit_event(Event::IssuerRemoved { issuer });
+            
+            Ok(())
+        }
+        
+        /// Register a canonical certificate type
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::register_certificate_type(required_fields.len() as u32))]
+        pub fn register_certificate_type(
+            origin: OriginFor<T>,
+            type_id: T::CertificateTypeId,
+            name: Vec<u8>,
+            default_validity: Moment,
+            required_fields: Vec<Vec<u8>>,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            
+            ensure!(!<CertificateTypes<T>>::contains_key(type_id), Error::<T>::CertificateTypeAlreadyExists);
+            
+            let required_fields = required_fields
+                .into_iter()
+                .map(Self::bounded_type_name)
+                .collect::<Result<Vec<_>, _>>()?;
+            
+            let info = CertificateTypeInfo {
+                name: Self::bounded_type_name(name)?,
+                default_validity,
+                required_fields: BoundedVec::try_from(required_fields)
+                    .map_err(|_| Error::<T>::TooManyRequiredFields)?,
+            };
+            
+            <CertificateTypes<T>>::insert(type_id, info);
+            
+            Self::deposit_event(Event::CertificateTypeRegistered { type_id });
+            
+            Ok(())
+        }
+        
+        /// Remove a certificate type, its prerequisite rule, its issuance fee and its accreditation
+        /// requirement from the registry
+        ///
+        /// Certificates already issued keep their type; no new ones can be issued.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::remove_certificate_type())]
+        pub fn remove_certificate_type(
+            origin: OriginFor<T>,
+            type_id: T::CertificateTypeId,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            
+            ensure!(<CertificateTypes<T>>::contains_key(type_id), Error::<T>::CertificateTypeNotFound);
+            
+            <CertificateTypes<T>>::remove(type_id);
+            <Prerequisites<T>>::remove(type_id);
+            <IssuanceFees<T>>::remove(type_id);
+            <AccreditationRequired<T>>::remove(type_id);
+            
+            Self::deposit_event(Event::CertificateTypeRemoved { type_id });
+            
+            Ok(())
+        }
+        
+        /// Issue certificates of one type to a whole cohort
+        ///
+        /// Each entry is `(recipient, metadata, expires_at)`. The batch is all-or-nothing: if any
+        /// certificate fails to issue, none are issued. The weight assumes maximum-length metadata
+        /// for every entry.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::issue_batch(certificates.len() as u32)
+            .saturating_add(Self::prerequisite_check_weight().saturating_mul(certificates.len() as u64)))]
+        pub fn issue_batch(
+            origin: OriginFor<T>,
+            cert_type: T::CertificateTypeId,
+            certificates: BoundedVec<
+                (<T::Lookup as StaticLookup>::Source, Vec<u8>, Option<Moment>),
+                T::MaxBatchSize,
+            >,
+        ) -> DispatchResult {
+            let signer = T::IssuerOrigin::ensure_origin(origin)?;
+            let issuer = Self::acting_issuer(&signer)?;
+            
+            ensure!(!certificates.is_empty(), Error::<T>::EmptyBatch);
+            let count = certificates.len() as u32;
+            
+            for (recipient, metadata, expires_at) in certificates {
+                let recipient = T::Lookup::lookup(recipient)?;
+                
+                let content = Self::inline_content(metadata)?;
+                let id = Self::do_issue(&issuer, &signer, &recipient, cert_type, content, expires_at, None)?;
+                
+                Self::deposit_event(Event::CertificateIssued {
+                    id,
+                    owner: recipient,
+                    issuer: issuer.clone(),
+                    issued_by: signer.clone(),
+                    cert_type,
+                });
+            }
+            
+            Self::deposit_event(Event::BatchIssued {
+                issuer,
+                cert_type,
+                count,
+            });
+            
+            Ok(())
+        }
+        
+        /// Remove revoked and superseded certificates from the caller's certificate list
+        ///
+        /// The certificate records themselves are kept for auditing; their deposits are returned to
+        /// their issuers.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::prune_certificates(T::MaxCertificatesPerAccount::get()))]
+        pub fn prune_certificates(origin: OriginFor<T>) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            
+            let mut pruned = Vec::new();
+            <AccountCertificates<T>>::mutate(&account, |certs| {
+                certs.retain(|id| {
+                    let in_force = <Certificates<T>>::get(id).map_or(false, |cert| {
+                        matches!(
+                            cert.status,
+                            CertificateStatus::Active | CertificateStatus::Suspended | CertificateStatus::ForceSuspended
+                        )
+                    });
+                    if !in_force {
+                        pruned.push(*id);
+                    }
+                    in_force
+                });
+            });
+            
+            ensure!(!pruned.is_empty(), Error::<T>::NothingToPrune);
+            
+            for id in pruned.iter() {
+                Self::release_deposit(*id);
+            }
+            let count = pruned.len() as u32;
+            
+            Self::deposit_event(Event::CertificatesPruned { account, count });
+            
+            Ok(())
+        }
+        
+        /// Issue a certificate whose metadata document is kept off chain
+        ///
+        /// Only the `T::Hashing` hash of the document and an optional locator are stored, so the
+        /// holder needs the document itself to prove what the certificate says. The document's
+        /// fields are not checked against the type's required fields.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::issue_cert_hashed().saturating_add(Self::prerequisite_check_weight()))]
+        pub fn issue_cert_hashed(
+            origin: OriginFor<T>,
+            recipient: <T::Lookup as StaticLookup>::Source,
+            cert_type: T::CertificateTypeId,
+            content_hash: T::Hash,
+            locator: Option<Vec<u8>>,
+            expires_at: Option<Moment>,
+        ) -> DispatchResult {
+            let signer = T::IssuerOrigin::ensure_origin(origin)?;
+            let issuer = Self::acting_issuer(&signer)?;
+            let recipient = T::Lookup::lookup(recipient)?;
+            
+            let locator = locator
+                .map(|locator| BoundedVec::try_from(locator).map_err(|_| Error::<T>::LocatorTooLong))
+                .transpose()?;
+            let content = CertificateContent::Hashed { hash: content_hash, locator };
+            let id = Self::do_issue(&issuer, &signer, &recipient, cert_type, content, expires_at, None)?;
+            
+            Self::deposit_event(Event::CertificateIssued {
+                id,
+                owner: recipient,
+                issuer,
+                issued_by: signer,
+                cert_type,
+            });
+            
+            Ok(())
+        }
+        
+        /// Set the certificate types a holder must also hold for a certificate of `type_id` to
+        /// be in force
+        ///
+        /// If `enforce_at_issuance` is set, certificates of `type_id` can only be issued to
+        /// holders meeting the rule. An empty `prerequisites` list removes the rule.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::set_prerequisites(prerequisites.len() as u32))]
+        pub fn set_prerequisites(
+            origin: OriginFor<T>,
+            type_id: T::CertificateTypeId,
+            prerequisites: Vec<T::CertificateTypeId>,
+            enforce_at_issuance: bool,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            
+            ensure!(<CertificateTypes<T>>::contains_key(type_id), Error::<T>::CertificateTypeNotFound);
+            for prerequisite in prerequisites.iter() {
+                ensure!(*prerequisite != type_id, Error::<T>::InvalidPrerequisite);
+                ensure!(<CertificateTypes<T>>::contains_key(prerequisite), Error::<T>::CertificateTypeNotFound);
+            }
+            
+            if prerequisites.is_empty() {
+                <Prerequisites<T>>::remove(type_id);
+            } else {
+                let certificate_types = BoundedVec::try_from(prerequisites.clone())
+                    .map_err(|_| Error::<T>::TooManyPrerequisites)?;
+                <Prerequisites<T>>::insert(type_id, PrerequisiteRule { certificate_types, enforce_at_issuance });
+            }
+            
+            Self::deposit_event(Event::PrerequisitesSet {
+                type_id,
+                prerequisites,
+                enforce_at_issuance,
+            });
+            
+            Ok(())
+        }
+        
+        /// Create a site profile managed by the caller
+        ///
+        /// Each requirement is `(cert_type, min_remaining_validity)`, the validity in milliseconds.
+        /// The caller pays `SiteProfileDeposit` until the profile is removed.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::create_site_profile(requirements.len() as u32))]
+        pub fn create_site_profile(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            requirements: Vec<(T::CertificateTypeId, Moment)>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            
+            let profile = SiteProfile {
+                owner: owner.clone(),
+                name: BoundedVec::try_from(name).map_err(|_| Error::<T>::SiteNameTooLong)?,
+                requirements: Self::site_requirements(requirements)?,
+                deposit: T::SiteProfileDeposit::get(),
+            };
+            
+            let site_id = <SiteCount<T>>::try_mutate(|id| -> Result<T::SiteId, DispatchError> {
+                let current_id = *id;
+                let next_id = Into::<u32>::into(current_id)
+                    .checked_add(1)
+                    .ok_or(Error::<T>::TooManySiteProfiles)?;
+                *id = next_id.into();
+                Ok(current_id)
+            })?;
+            
+            T::Currency::reserve(&owner, profile.deposit)?;
+            <SiteProfiles<T>>::insert(site_id, profile);
+            
+            Self::deposit_event(Event::SiteProfileCreated { site_id, owner });
+            
+            Ok(())
+        }
+        
+        /// Replace the name and requirements of a site profile
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::update_site_profile(requirements.len() as u32))]
+        pub fn update_site_profile(
+            origin: OriginFor<T>,
+            site_id: T::SiteId,
+            name: Vec<u8>,
+            requirements: Vec<(T::CertificateTypeId, Moment)>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            
+            <SiteProfiles<T>>::try_mutate(site_id, |profile_opt| {
+                let profile = profile_opt.as_mut().ok_or(Error::<T>::SiteProfileNotFound)?;
+                
+                ensure!(profile.owner == owner, Error::<T>::NotSiteOwner);
+                
+                profile.name = BoundedVec::try_from(name).map_err(|_| Error::<T>::SiteNameTooLong)?;
+                profile.requirements = Self::site_requirements(requirements)?;
+                
+                Ok::<(), DispatchError>(())
+            })?;
+            
+            Self::deposit_event(Event::SiteProfileUpdated { site_id });
+            
+            Ok(())
+        }
+        
+        /// Remove a site profile
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::remove_site_profile())]
+        pub fn remove_site_profile(
+            origin: OriginFor<T>,
+            site_id: T::SiteId,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            
+            let profile = <SiteProfiles<T>>::get(site_id).ok_or(Error::<T>::SiteProfileNotFound)?;
+            ensure!(profile.owner == owner, Error::<T>::NotSiteOwner);
+            
+            T::Currency::unreserve(&owner, profile.deposit);
+            <SiteProfiles<T>>::remove(site_id);
+            
+            Self::deposit_event(Event::SiteProfileRemoved { site_id });
+            
+            Ok(())
+        }
+        
+        /// Authorize an account to issue, revoke, suspend, reinstate and renew certificates on
+        /// the calling issuer's behalf
+        ///
+        /// Certificates issued by a delegate belong to the issuer, so any of its delegates can
+        /// manage them after the delegate that issued them has left.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::add_delegate())]
+        pub fn add_delegate(
+            origin: OriginFor<T>,
+            delegate: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            let issuer = Self::key_issuer(&T::IssuerOrigin::ensure_origin(origin)?)?;
+            let delegate = T::Lookup::lookup(delegate)?;
+            
+            ensure!(<Issuers<T>>::contains_key(&issuer), Error::<T>::IssuerNotRegistered);
+            ensure!(!Self::is_issuer_account(&delegate), Error::<T>::DelegateIsIssuer);
+            ensure!(!<Delegates<T>>::contains_key(&delegate), Error::<T>::AlreadyDelegate);
+            
+            <IssuerDelegates<T>>::try_mutate(&issuer, |delegates| {
+                delegates.try_push(delegate.clone()).map_err(|_| Error::<T>::TooManyDelegates)
+            })?;
+            <Delegates<T>>::insert(&delegate, &issuer);
+            
+            Self::deposit_event(Event::DelegateAdded { issuer, delegate });
+            
+            Ok(())
+        }
+        
+        /// Withdraw a delegate's authorization
+        ///
+        /// Called by the issuer, or by the delegate itself to step down.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::remove_delegate())]
+        pub fn remove_delegate(
+            origin: OriginFor<T>,
+            delegate: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let delegate = T::Lookup::lookup(delegate)?;
+            
+            let issuer = <Delegates<T>>::get(&delegate).ok_or(Error::<T>::NotDelegate)?;
+            ensure!(
+                who == delegate || Self::key_issuer(&who).map_or(false, |who| who == issuer),
+                Error::<T>::NotDelegate
+            );
+            
+            <Delegates<T>>::remove(&delegate);
+            <IssuerDelegates<T>>::mutate(&issuer, |delegates| delegates.retain(|account| account != &delegate));
+            
+            Self::deposit_event(Event::DelegateRemoved { issuer, delegate });
+            
+            Ok(())
+        }
+        
+        /// Replace an issuer's signing key, keeping its identity and its certificates
+        ///
+        /// Called with the current key for a routine rotation, or by the registrar when the key
+        /// is lost or compromised. The retired key can no longer act for the issuer and cannot
+        /// be used as a key again; the key history records which key was valid when.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::rotate_issuer_key())]
+        pub fn rotate_issuer_key(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+            new_key: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            let issuer = T::Lookup::lookup(issuer)?;
+            let new_key = T::Lookup::lookup(new_key)?;
+            
+            if T::RegistrarOrigin::try_origin(origin.clone()).is_err() {
+                let signer = T::IssuerOrigin::ensure_origin(origin)?;
+                ensure!(Self::key_issuer(&signer)? == issuer, DispatchError::BadOrigin);
+            }
+            
+            let info = <Issuers<T>>::get(&issuer).ok_or(Error::<T>::IssuerNotRegistered)?;
+            
+            ensure!(
+                !<Delegates<T>>::contains_key(&new_key) && !Self::is_issuer_account(&new_key),
+                Error::<T>::KeyInUse
+            );
+            
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut history = <IssuerKeys<T>>::get(&issuer).into_inner();
+            let old_key = match history.last_mut() {
+                Some(current) => {
+                    current.valid_until = Some(now);
+                    current.key.clone()
+                }
+                // The issuer has signed with its identity account since registration
+                None => {
+                    history.push(IssuerKey {
+                        key: issuer.clone(),
+                        valid_from: info.registered_at,
+                        valid_until: Some(now),
+                    });
+                    issuer.clone()
+                }
+            };
+            // Keep room for the new key by dropping the oldest entries
+            let max = T::MaxKeyHistory::get() as usize;
+            if history.len() >= max {
+                history.drain(..history.len() + 1 - max);
+            }
+            history.push(IssuerKey { key: new_key.clone(), valid_from: now, valid_until: None });
+            
+            <IssuerKeys<T>>::insert(&issuer, BoundedVec::truncate_from(history));
+            <SigningKeys<T>>::remove(&old_key);
+            <SigningKeys<T>>::insert(&new_key, &issuer);
+            <RetiredKeys<T>>::insert(&old_key, &issuer);
+            
+            Self::deposit_event(Event::IssuerKeyRotated { issuer, old_key, new_key });
+            
+            Ok(())
+        }
+        
+        /// Set the fee paid to the treasury for each certificate issued of a type
+        ///
+        /// A zero fee removes it.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_issuance_fee())]
+        pub fn set_issuance_fee(
+            origin: OriginFor<T>,
+            type_id: T::CertificateTypeId,
+            fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            
+            ensure!(<CertificateTypes<T>>::contains_key(type_id), Error::<T>::CertificateTypeNotFound);
+            
+            if fee.is_zero() {
+                <IssuanceFees<T>>::remove(type_id);
+            } else {
+                <IssuanceFees<T>>::insert(type_id, fee);
+            }
+            
+            Self::deposit_event(Event::IssuanceFeeSet { type_id, fee });
+            
+            Ok(())
+        }
+        
+        /// Offer a new certificate to an account, which must accept it before it is in force
+        ///
+        /// The offer is checked and charged as on issuance and lapses after `OfferPeriod` blocks.
+        /// Its expiry is counted from the offer, not the acceptance.
+        #[pallet::call_index(22)]
+        #[pallet::weight(
+            T::WeightInfo::offer_cert(metadata.len() as u32).saturating_add(Self::prerequisite_check_weight())
+        )]
+        pub fn offer_cert(
+            origin: OriginFor<T>,
+            recipient: <T::Lookup as StaticLookup>::Source,
+            cert_type: T::CertificateTypeId,
+            metadata: Vec<u8>,
+            expires_at: Option<Moment>,
+        ) -> DispatchResult {
+            let signer = T::IssuerOrigin::ensure_origin(origin)?;
+            let issuer = Self::acting_issuer(&signer)?;
+            let recipient = T::Lookup::lookup(recipient)?;
+            
+            let content = Self::inline_content(metadata)?;
+            let mut cert = Self::new_certificate(&issuer, &signer, &recipient, cert_type, content, expires_at)?;
+            cert.status = CertificateStatus::Pending;
+            
+            let id = cert.id;
+            let lapses_at = cert.issued_at.saturating_add(T::OfferPeriod::get());
+            <Certificates<T>>::insert(id, cert);
+            <IssuerCertificates<T>>::insert(&issuer, id, ());
+            <PendingOffers<T>>::insert(&recipient, id, lapses_at);
+            
+            Self::deposit_event(Event::CertificateOffered {
+                id,
+                owner: recipient,
+                issuer,
+                issued_by: signer,
+                cert_type,
+                lapses_at,
+            });
+            
+            Ok(())
+        }
+        
+        /// Accept a certificate offered to the caller, putting it in force
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::accept_cert())]
+        pub fn accept_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let mut cert = <Certificates<T>>::get(cert_id).ok_or(Error::<T>::CertificateNotFound)?;
+            ensure!(cert.owner == who, Error::<T>::NotCertificateOwner);
+            
+            let lapses_at = <PendingOffers<T>>::get(&who, cert_id).ok_or(Error::<T>::CertificateNotPending)?;
+            ensure!(<frame_system::Pallet<T>>::block_number() < lapses_at, Error::<T>::OfferLapsed);
+            
+            <PendingOffers<T>>::remove(&who, cert_id);
+            cert.status = CertificateStatus::Active;
+            Self::place_certificate(&cert)?;
+            <Certificates<T>>::insert(cert_id, cert);
+            
+            Self::deposit_event(Event::CertificateAccepted { id: cert_id, owner: who });
+            
+            Ok(())
+        }
+        
+        /// Reject a pending certificate offer, removing it and returning its deposit
+        ///
+        /// The holder may reject an offer and its issuer or a delegate may withdraw it at any
+        /// time; once it has lapsed anyone may clear it. The issuance fee is not refunded.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::reject_cert())]
+        pub fn reject_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let cert = <Certificates<T>>::get(cert_id).ok_or(Error::<T>::CertificateNotFound)?;
+            let lapses_at = <PendingOffers<T>>::get(&cert.owner, cert_id).ok_or(Error::<T>::CertificateNotPending)?;
+            
+            ensure!(
+                who == cert.owner
+                    || Self::acting_issuer(&who).ok().as_ref() == Some(&cert.issuer)
+                    || <frame_system::Pallet<T>>::block_number() >= lapses_at,
+                Error::<T>::NotCertificateOwner
+            );
+            
+            Self::withdraw_offer(cert);
+            
+            Ok(())
+        }
+        
+        /// Burn one of the caller's certificates, erasing its metadata
+        ///
+        /// The certificate leaves the caller's certificate list and its record is replaced by a
+        /// tombstone keeping its issuer, the hash of its metadata document and its status, so the
+        /// document can still be verified by whoever holds it; the issuer's listing reports the
+        /// tombstone in the certificate's place. The deposit is returned to the
+        /// issuer, and the certificate's endorsements are dropped with their deposits returned.
+        /// A certificate in a renewal chain is cut out of it, linking its predecessor and successor.
+        /// Pending offers are rejected instead.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::burn_cert(T::MaxEndorsementsPerCertificate::get()))]
+        pub fn burn_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let cert = <Certificates<T>>::get(cert_id).ok_or(Error::<T>::CertificateNotFound)?;
+            ensure!(cert.owner == who, Error::<T>::NotCertificateOwner);
+            ensure!(cert.status != CertificateStatus::Pending, Error::<T>::CertificatePending);
+            
+            <AccountCertificates<T>>::mutate(&who, |certs| certs.retain(|id| *id != cert_id));
+            <ExpiryIndex<T>>::remove(Self::expiry_bucket(cert.expires_at), cert_id);
+            <CertificateAccreditations<T>>::remove(cert_id);
+            <Certificates<T>>::remove(cert_id);
+            T::Currency::unreserve(&cert.issuer, cert.deposit);
+            
+            // Link the neighbours in the renewal chain to each other so it stays intact
+            if let Some(previous) = cert.supersedes {
+                <Certificates<T>>::mutate(previous, |previous_opt| {
+                    if let Some(previous) = previous_opt {
+                        previous.superseded_by = cert.superseded_by;
+                    }
+                });
+            }
+            if let Some(next) = cert.superseded_by {
+                <Certificates<T>>::mutate(next, |next_opt| {
+                    if let Some(next) = next_opt {
+                        next.supersedes = cert.supersedes;
+                    }
+                });
+            }
+            for endorsement in <Endorsements<T>>::take(cert_id) {
+                T::Currency::unreserve(&endorsement.endorser, endorsement.deposit);
+            }
+            
+            let content_hash = match cert.content {
+                CertificateContent::Inline(metadata) => T::Hashing::hash(&metadata[..]),
+                CertificateContent::Hashed { hash, .. } => hash,
+            };
+            <Tombstones<T>>::insert(
+                cert_id,
+                Tombstone {
+                    issuer: cert.issuer,
+                    content_hash,
+                    status: cert.status,
+                    burned_at: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+            
+            Self::deposit_event(Event::CertificateBurned { id: cert_id, owner: who });
+            
+            Ok(())
+        }
+        
+        /// Register an accreditation body, which may then accredit issuers
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::register_accreditor())]
+        pub fn register_accreditor(
+            origin: OriginFor<T>,
+            accreditor: <T::Lookup as StaticLookup>::Source,
+            name: Vec<u8>,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            let accreditor = T::Lookup::lookup(accreditor)?;
+            
+            ensure!(!<Accreditors<T>>::contains_key(&accreditor), Error::<T>::AccreditorAlreadyRegistered);
+            
+            let info = AccreditorInfo {
+                name: Self::bounded_field(name)?,
+                registered_at: <frame_system::Pallet<T>>::block_number(),
+            };
+            
+            <Accreditors<T>>::insert(&accreditor, info);
+            
+            Self::deposit_event(Event::AccreditorRegistered { accreditor });
+            
+            Ok(())
+        }
+        
+        /// Remove an accreditation body from the registry
+        ///
+        /// The accreditations it granted no longer count for new certificates; certificates already
+        /// issued under them keep their record.
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::remove_accreditor())]
+        pub fn remove_accreditor(
+            origin: OriginFor<T>,
+            accreditor: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            let accreditor = T::Lookup::lookup(accreditor)?;
+            
+            ensure!(<Accreditors<T>>::contains_key(&accreditor), Error::<T>::AccreditorNotRegistered);
+            
+            <Accreditors<T>>::remove(&accreditor);
+            
+            Self::deposit_event(Event::AccreditorRemoved { accreditor });
+            
+            Ok(())
+        }
+        
+        /// Accredit a registered issuer for a certificate type it may issue until `expires_at`
+        ///
+        /// `expires_at` is in milliseconds since the Unix epoch. An accreditor may renew its own
+        /// accreditation at any time; another accreditor's only once it has ended.
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::accredit_issuer())]
+        pub fn accredit_issuer(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+            cert_type: T::CertificateTypeId,
+            expires_at: Moment,
+        ) -> DispatchResult {
+            let accreditor = ensure_signed(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            
+            ensure!(<Accreditors<T>>::contains_key(&accreditor), Error::<T>::AccreditorNotRegistered);
+            let info = <Issuers<T>>::get(&issuer).ok_or(Error::<T>::IssuerNotRegistered)?;
+            ensure!(<CertificateTypes<T>>::contains_key(cert_type), Error::<T>::CertificateTypeNotFound);
+            ensure!(info.certificate_types.contains(&cert_type), Error::<T>::CertificateTypeNotAllowed);
+            
+            let now = Self::now();
+            ensure!(expires_at > now, Error::<T>::InvalidAccreditationExpiry);
+            if let Some(current) = Self::current_accreditation(&issuer, cert_type) {
+                ensure!(current.accreditor == accreditor, Error::<T>::AccreditedByAnother);
+            }
+            
+            <Accreditations<T>>::insert(
+                &issuer,
+                cert_type,
+                Accreditation { accreditor: accreditor.clone(), granted_at: now, expires_at },
+            );
+            
+            Self::deposit_event(Event::IssuerAccredited { issuer, cert_type, accreditor, expires_at });
+            
+            Ok(())
+        }
+        
+        /// Withdraw an accreditation the caller granted
+        ///
+        /// Certificates the issuer already issued under it keep their record.
+        #[pallet::call_index(29)]
+        #[pallet::weight(T::WeightInfo::withdraw_accreditation())]
+        pub fn withdraw_accreditation(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+            cert_type: T::CertificateTypeId,
+        ) -> DispatchResult {
+            let accreditor = ensure_signed(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            
+            let accreditation = <Accreditations<T>>::get(&issuer, cert_type).ok_or(Error::<T>::AccreditationNotFound)?;
+            ensure!(accreditation.accreditor == accreditor, Error::<T>::NotAccreditor);
+            
+            <Accreditations<T>>::remove(&issuer, cert_type);
+            
+            Self::deposit_event(Event::AccreditationWithdrawn { issuer, cert_type, accreditor });
+            
+            Ok(())
+        }
+        
+        /// Set whether certificates of a type are only valid if their issuer was accredited for the
+        /// type when issuing them
+        ///
+        /// The requirement applies to certificates already issued as well as new ones.
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::set_accreditation_required())]
+        pub fn set_accreditation_required(
+            origin: OriginFor<T>,
+            type_id: T::CertificateTypeId,
+            required: bool,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            
+            ensure!(<CertificateTypes<T>>::contains_key(type_id), Error::<T>::CertificateTypeNotFound);
+            
+            if required {
+                <AccreditationRequired<T>>::insert(type_id, ());
+            } else {
+                <AccreditationRequired<T>>::remove(type_id);
+            }
+            
+            Self::deposit_event(Event::AccreditationRequirementSet { type_id, required });
+            
+            Ok(())
+        }
+        
+        /// Revoke any certificate, whoever issued it
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::force_revoke())]
+        pub fn force_revoke(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+            reason: RevocationReason,
+            note_hash: Option<T::Hash>,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            
+            <Certificates<T>>::try_mutate(cert_id, |cert_opt| {
+                let cert = cert_opt.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+                
+                ensure!(cert.status != CertificateStatus::Revoked, Error::<T>::CertificateAlreadyRevoked);
+                ensure!(cert.status != CertificateStatus::Pending, Error::<T>::CertificatePending);
+                
+                let revoked_at = Self::revoke(cert, reason, note_hash);
+                
+                Self::deposit_event(Event::CertificateForceRevoked {
+                    id: cert_id,
+                    reason,
+                    note_hash,
+                    revoked_at,
+                });
+                
+                Ok(())
+            })
+        }
+        
+        /// Suspend an issuer and suspend or revoke all of its certificates
+        ///
+        /// The issuer is suspended at once, even if it already was. Its certificates are processed
+        /// `MaxCascadePerBlock` at a time from the following blocks on, and its pending offers are
+        /// withdrawn. A new action for the same issuer replaces one still in progress. Issuers
+        /// already removed from the registry can still have their certificates cascaded, and
+        /// suspended certificates can only be reinstated by the regulator.
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::force_suspend_issuer())]
+        pub fn force_suspend_issuer(
+            origin: OriginFor<T>,
+            issuer: <T::Lookup as StaticLookup>::Source,
+            action: CascadeAction,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            
+            ensure!(
+                <Issuers<T>>::contains_key(&issuer)
+                    || <IssuerCertificates<T>>::iter_key_prefix(&issuer).next().is_some(),
+                Error::<T>::IssuerNotRegistered
+            );
+            <Issuers<T>>::mutate(&issuer, |info_opt| {
+                if let Some(info) = info_opt {
+                    info.status = IssuerStatus::Suspended;
+                }
+            });
+            <Cascades<T>>::insert(&issuer, Cascade { action, last: None });
+            
+            Self::deposit_event(Event::IssuerForceSuspended { issuer, action });
+            
+            Ok(())
+        }
+        
+        /// Endorse a claim about the holder of an active certificate
+        ///
+        /// `claim_hash` is the `T::Hashing` hash of a claim document kept off chain, e.g. hours of
+        /// supervised work. The endorser pays `EndorsementDeposit` until the endorsement is revoked.
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::WeightInfo::endorse_cert())]
+        pub fn endorse_cert(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+            claim_hash: T::Hash,
+        ) -> DispatchResult {
+            let endorser = ensure_signed(origin)?;
+            
+            let cert = <Certificates<T>>::get(cert_id).ok_or(Error::<T>::CertificateNotFound)?;
+            ensure!(cert.status == CertificateStatus::Active, Error::<T>::CertificateNotActive);
+            ensure!(cert.owner != endorser, Error::<T>::SelfEndorsement);
+            
+            <Endorsements<T>>::try_mutate(cert_id, |endorsements| -> DispatchResult {
+                ensure!(
+                    !endorsements
+                        .iter()
+                        .any(|endorsement| endorsement.endorser == endorser && endorsement.claim_hash == claim_hash),
+                    Error::<T>::AlreadyEndorsed
+                );
+                
+                let deposit = T::EndorsementDeposit::get();
+                endorsements
+                    .try_push(Endorsement {
+                        endorser: endorser.clone(),
+                        claim_hash,
+                        endorsed_at: <frame_system::Pallet<T>>::block_number(),
+                        deposit,
+                    })
+                    .map_err(|_| Error::<T>::TooManyEndorsements)?;
+                T::Currency::reserve(&endorser, deposit)
+            })?;
+            
+            Self::deposit_event(Event::CertificateEndorsed { id: cert_id, endorser, claim_hash });
+            
+            Ok(())
+        }
+        
+        /// Revoke an endorsement, returning its deposit to the endorser
+        ///
+        /// The endorser may revoke its endorsement and the certificate's holder may dismiss it.
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::WeightInfo::revoke_endorsement())]
+        pub fn revoke_endorsement(
+            origin: OriginFor<T>,
+            cert_id: T::CertificateId,
+            endorser: <T::Lookup as StaticLookup>::Source,
+            claim_hash: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let endorser = T::Lookup::lookup(endorser)?;
+            
+            if who != endorser {
+                let cert = <Certificates<T>>::get(cert_id).ok_or(Error::<T>::CertificateNotFound)?;
+                ensure!(cert.owner == who, Error::<T>::NotCertificateOwner);
+            }
+            
+            <Endorsements<T>>::try_mutate_exists(cert_id, |endorsements_opt| {
+                let endorsements = endorsements_opt.as_mut().ok_or(Error::<T>::EndorsementNotFound)?;
+                let index = endorsements
+                    .iter()
+                    .position(|endorsement| endorsement.endorser == endorser && endorsement.claim_hash == claim_hash)
+                    .ok_or(Error::<T>::EndorsementNotFound)?;
+                
+                let endorsement = endorsements.remove(index);
+                T::Currency::unreserve(&endorsement.endorser, endorsement.deposit);
+                if endorsements.is_empty() {
+                    *endorsements_opt = None;
+                }
+                
+                Ok::<(), DispatchError>(())
+            })?;
+            
+            Self::deposit_event(Event::EndorsementRevoked { id: cert_id, endorser, claim_hash });
+            
+            Ok(())
+        }
+        
+        /// Claim a certificate with a voucher an issuer signed off chain
+        ///
+        /// `signer` is the issuer, one of its delegates or its current signing key, and `signature`
+        /// its signature of `voucher_payload(voucher)`. The certificate is issued to the caller as if
+        /// the signer had issued it, so the issuer pays the issuance fee and deposit. Only the
+        /// voucher's recipient can claim it, and each voucher nonce can be claimed once per issuer.
+        /// A voucher may stay claimable for at most `MaxVoucherValidity`; its nonce is pruned once
+        /// it can no longer be claimed.
+        #[pallet::call_index(35)]
+        #[pallet::weight(
+            T::WeightInfo::claim_cert(voucher.metadata.len() as u32).saturating_add(Self::prerequisite_check_weight())
+        )]
+        pub fn claim_cert(
+            origin: OriginFor<T>,
+            voucher: Voucher<T>,
+            signer: T::AccountId,
+            signature: T::OffchainSignature,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            ensure!(
+                signature.verify(&Self::voucher_payload(&voucher)[..], &signer),
+                Error::<T>::InvalidVoucherSignature
+            );
+            ensure!(Self::acting_issuer(&signer)? == voucher.issuer, Error::<T>::SignerNotIssuer);
+            let now = Self::now();
+            ensure!(voucher.valid_until > now, Error::<T>::VoucherExpired);
+            ensure!(
+                voucher.valid_until <= now.saturating_add(T::MaxVoucherValidity::get()),
+                Error::<T>::VoucherValidityTooLong
+            );
+            ensure!(voucher.recipient == who, Error::<T>::NotVoucherRecipient);
+            ensure!(!<UsedNonces<T>>::contains_key(&voucher.issuer, voucher.nonce), Error::<T>::VoucherAlreadyUsed);
+            
+            let Voucher { issuer, cert_type, metadata, expires_at, nonce, valid_until, .. } = voucher;
+            let content = CertificateContent::Inline(metadata);
+            let id = Self::do_issue(&issuer, &signer, &who, cert_type, content, expires_at, None)?;
+            <UsedNonces<T>>::insert(&issuer, nonce, valid_until);
+            <NonceExpiries<T>>::insert(Self::expiry_bucket(valid_until), (&issuer, nonce), ());
+            
+            Self::deposit_event(Event::CertificateClaimed {
+                id,
+                owner: who,
+                issuer,
+                issued_by: signer,
+                cert_type,
+                nonce,
+            });
+            
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Get the next certificate ID
+        fn next_certificate_id() -> Result<T::CertificateId, DispatchError> {
             <CertificateCount<T>>::try_mutate(|id| {
                 let current_id = *id;
-                *id = id.checked_add(&T::CertificateId::from(1)).ok_or(Error::<T>::CertificateAlreadyExists)?;
+                let next_id = Into::<u32>::into(current_id)
+                    .checked_add(1)
+                    .ok_or(Error::<T>::CertificateAlreadyExists)?;
+                *id = next_id.into();
                 Ok(current_id)
             })
         }
         
+        /// Resolve the issuer an account signs for: the issuer it is a delegate of, or the
+        /// issuer whose key it is
+        fn acting_issuer(signer: &T::AccountId) -> Result<T::AccountId, DispatchError> {
+            match <Delegates<T>>::get(signer) {
+                Some(issuer) => Ok(issuer),
+                None => Self::key_issuer(signer),
+            }
+        }
+        
+        /// Resolve the issuer identity a signing key belongs to
+        ///
+        /// An issuer that never rotated its key signs with its identity account, which stops
+        /// acting for it once rotated out like any other retired key.
+        fn key_issuer(signer: &T::AccountId) -> Result<T::AccountId, DispatchError> {
+            if let Some(issuer) = <SigningKeys<T>>::get(signer) {
+                return Ok(issuer);
+            }
+            ensure!(!<RetiredKeys<T>>::contains_key(signer), Error::<T>::IssuerKeyRetired);
+            Ok(signer.clone())
+        }
+        
+        /// Whether an account is an issuer identity or a current or retired signing key
+        fn is_issuer_account(account: &T::AccountId) -> bool {
+            <Issuers<T>>::contains_key(account)
+                || <IssuerKeys<T>>::contains_key(account)
+                || <SigningKeys<T>>::contains_key(account)
+                || <RetiredKeys<T>>::contains_key(account)
+        }
+        
+        /// Ensure an account is a registered, active issuer
+        fn ensure_active_issuer(issuer: &T::AccountId) -> Result<IssuerInfo<T>, DispatchError> {
+            let info = <Issuers<T>>::get(issuer).ok_or(Error::<T>::IssuerNotRegistered)?;
+            ensure!(info.status == IssuerStatus::Active, Error::<T>::IssuerSuspended);
+            Ok(info)
+        }
+        
+        /// Get the accreditation an issuer holds for a certificate type, if it is in force and its
+        /// accreditor is still registered
+        fn current_accreditation(issuer: &T::AccountId, cert_type: T::CertificateTypeId) -> Option<Accreditation<T>> {
+            <Accreditations<T>>::get(issuer, cert_type).filter(|accreditation| {
+                accreditation.expires_at > Self::now() && <Accreditors<T>>::contains_key(&accreditation.accreditor)
+            })
+        }
+        
+        /// Bound an issuer or accreditor registry field
+        fn bounded_field(field: Vec<u8>) -> Result<BoundedVec<u8, T::MaxIssuerFieldLength>, DispatchError> {
+            BoundedVec::try_from(field).map_err(|_| Error::<T>::IssuerFieldTooLong.into())
+        }
+        
+        /// Bound a certificate type name or field name, which must be valid UTF-8
+        fn bounded_type_name(name: Vec<u8>) -> Result<BoundedVec<u8, T::MaxTypeNameLength>, DispatchError> {
+            ensure!(sp_std::str::from_utf8(&name).is_ok(), Error::<T>::InvalidTypeName);
+            BoundedVec::try_from(name).map_err(|_| Error::<T>::InvalidTypeName.into())
+        }
+        
+        /// Bound metadata to be stored on chain
+        fn inline_content(metadata: Vec<u8>) -> Result<CertificateContent<T>, DispatchError> {
+            BoundedVec::try_from(metadata)
+                .map(CertificateContent::Inline)
+                .map_err(|_| Error::<T>::MetadataTooLong.into())
+        }
+        
+        /// Ensure metadata is a JSON object carrying every field required by its type
+        fn validate_metadata(type_info: &CertificateTypeInfo<T>, metadata: &[u8]) -> DispatchResult {
+            let value: serde_json::Value = serde_json::from_slice(metadata)
+                .map_err(|_| Error::<T>::InvalidMetadata)?;
+            let fields = value.as_object().ok_or(Error::<T>::InvalidMetadata)?;
+            
+            for field in type_info.required_fields.iter() {
+                let field = sp_std::str::from_utf8(field).map_err(|_| Error::<T>::InvalidMetadata)?;
+                ensure!(fields.contains_key(field), Error::<T>::MissingMetadataField);
+            }
+            
+            Ok(())
+        }
+        
+        /// Issue a certificate after checking the issuer may issue its type
+        ///
+        /// `issued_by` is the account that signed for the issuer. If `supersedes` is set the new
+        /// certificate replaces that one in the holder's certificate list.
+        pub(crate) fn do_issue(
+            issuer: &T::AccountId,
+            issued_by: &T::AccountId,
+            recipient: &T::AccountId,
+            cert_type: T::CertificateTypeId,
+            content: CertificateContent<T>,
+            expires_at: Option<Moment>,
+            supersedes: Option<T::CertificateId>,
+        ) -> Result<T::CertificateId, DispatchError> {
+            let mut cert = Self::new_certificate(issuer, issued_by, recipient, cert_type, content, expires_at)?;
+            cert.supersedes = supersedes;
+            Self::place_certificate(&cert)?;
+            
+            // Store the certificate
+            let id = cert.id;
+            <Certificates<T>>::insert(id, cert);
+            <IssuerCertificates<T>>::insert(issuer, id, ());
+            
+            Ok(id)
+        }
+        
+        /// Create an active certificate, not yet stored, after checking the issuer may issue its type
+        ///
+        /// Inline metadata is checked against the type's required fields; hashed metadata cannot be
+        /// checked on chain. The issuer pays the type's issuance fee and the certificate's storage
+        /// deposit, and the accreditation it holds for the type, if any, is recorded.
+        fn new_certificate(
+            issuer: &T::AccountId,
+            issued_by: &T::AccountId,
+            recipient: &T::AccountId,
+            cert_type: T::CertificateTypeId,
+            content: CertificateContent<T>,
+            expires_at: Option<Moment>,
+        ) -> Result<Certificate<T>, DispatchError> {
+            // Ensure the issuer is registered, active and allowed to issue this type
+            let issuer_info = Self::ensure_active_issuer(issuer)?;
+            ensure!(issuer_info.certificate_types.contains(&cert_type), Error::<T>::CertificateTypeNotAllowed);
+
+            let type_info = <CertificateTypes<T>>::get(cert_type).ok_or(Error::<T>::CertificateTypeNotFound)?;
+
+            // Validate inline metadata contents
+            if let CertificateContent::Inline(metadata) = &content {
+                Self::validate_metadata(&type_info, metadata)?;
+            }
+            
+            // Ensure the recipient meets the type's prerequisites if they are enforced
+            if let Some(rule) = <Prerequisites<T>>::get(cert_type).filter(|rule| rule.enforce_at_issuance) {
+                ensure!(Self::prerequisites_met(&rule, recipient), Error::<T>::PrerequisiteNotMet);
+            }
+
+            // Charge the issuance fee and hold the storage deposit
+            if let Some(fee) = <IssuanceFees<T>>::get(cert_type) {
+                T::Currency::transfer(issuer, &T::TreasuryAccount::get(), fee, ExistenceRequirement::KeepAlive)?;
+            }
+            let deposit = Self::certificate_deposit(&content);
+            T::Currency::reserve(issuer, deposit)?;
+
+            let issued_at = <frame_system::Pallet<T>>::block_number();
+            let expires_at = match expires_at {
+                Some(expires_at) => expires_at,
+                None if type_info.default_validity.is_zero() => Zero::zero(),
+                None => Self::now().saturating_add(type_info.default_validity),
+            };
+
+            let id = Self::next_certificate_id()?;
+            if let Some(accreditation) = Self::current_accreditation(issuer, cert_type) {
+                <CertificateAccreditations<T>>::insert(id, accreditation);
+            }
+
+            Ok(Certificate {
+                id,
+                owner: recipient.clone(),
+                issuer: issuer.clone(),
+                issued_by: issued_by.clone(),
+                cert_type,
+                content,
+                deposit,
+                issued_at,
+                status: CertificateStatus::Active,
+                revocation: None,
+                expires_at,
+                supersedes: None,
+                superseded_by: None,
+            })
+        }
+        
+        /// Put a certificate in force on its holder's record
+        fn place_certificate(cert: &Certificate<T>) -> DispatchResult {
+            // Index the expiry so `on_initialize` can report it
+            if cert.expires_at > Self::now() {
+                <ExpiryIndex<T>>::insert(Self::expiry_bucket(cert.expires_at), cert.id, ());
+            }
+
+            // Update account certificates, replacing the renewed certificate if there is one
+            <AccountCertificates<T>>::try_mutate(&cert.owner, |certs| {
+                match cert.supersedes.and_then(|old| certs.iter().position(|cert_id| *cert_id == old)) {
+                    Some(index) => {
+                        if let Some(slot) = certs.get_mut(index) {
+                            *slot = cert.id;
+                        }
+                        Ok(())
+                    }
+                    None => certs.try_push(cert.id).map_err(|_| Error::<T>::TooManyCertificates.into()),
+                }
+            })
+        }
+        
+        /// Deposit for a certificate: the base deposit plus a deposit per byte of metadata stored
+        fn certificate_deposit(content: &CertificateContent<T>) -> BalanceOf<T> {
+            let bytes = match content {
+                CertificateContent::Inline(metadata) => metadata.len(),
+                CertificateContent::Hashed { hash, locator } => {
+                    hash.as_ref().len() + locator.as_ref().map_or(0, |locator| locator.len())
+                }
+            };
+            T::CertificateDepositBase::get()
+                .saturating_add(T::CertificateDepositPerByte::get().saturating_mul((bytes as u32).into()))
+        }
+        
+        /// Revoke a certificate in place, taking it out of the expiry index, and return when
+        fn revoke(
+            cert: &mut Certificate<T>,
+            reason: RevocationReason,
+            note_hash: Option<T::Hash>,
+        ) -> T::BlockNumber {
+            let revoked_at = <frame_system::Pallet<T>>::block_number();
+            <ExpiryIndex<T>>::remove(Self::expiry_bucket(cert.expires_at), cert.id);
+            cert.status = CertificateStatus::Revoked;
+            cert.revocation = Some(RevocationInfo { reason, note_hash, revoked_at });
+            revoked_at
+        }
+        
+        /// Remove a pending offer and return its deposit to the issuer
+        fn withdraw_offer(cert: Certificate<T>) {
+            <PendingOffers<T>>::remove(&cert.owner, cert.id);
+            <IssuerCertificates<T>>::remove(&cert.issuer, cert.id);
+            <CertificateAccreditations<T>>::remove(cert.id);
+            <Certificates<T>>::remove(cert.id);
+            T::Currency::unreserve(&cert.issuer, cert.deposit);
+            
+            Self::deposit_event(Event::CertificateRejected { id: cert.id, owner: cert.owner });
+        }
+        
+        /// Return a certificate's deposit to its issuer
+        fn release_deposit(cert_id: T::CertificateId) {
+            <Certificates<T>>::mutate(cert_id, |cert_opt| {
+                if let Some(cert) = cert_opt {
+                    T::Currency::unreserve(&cert.issuer, sp_std::mem::take(&mut cert.deposit));
+                }
+            });
+        }
+        
+        /// Bound site requirements, which must refer to registered certificate types
+        fn site_requirements(
+            requirements: Vec<(T::CertificateTypeId, Moment)>,
+        ) -> Result<BoundedVec<SiteRequirement<T>, T::MaxSiteRequirements>, DispatchError> {
+            let requirements = requirements
+                .into_iter()
+                .map(|(cert_type, min_remaining_validity)| -> Result<SiteRequirement<T>, DispatchError> {
+                    ensure!(<CertificateTypes<T>>::contains_key(cert_type), Error::<T>::CertificateTypeNotFound);
+                    Ok(SiteRequirement { cert_type, min_remaining_validity })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            BoundedVec::try_from(requirements).map_err(|_| Error::<T>::TooManySiteRequirements.into())
+        }
+        
+        /// Weight of checking prerequisites at issuance against a full certificate list. The
+        /// issuance benchmarks run without a prerequisite rule, so this is charged on top of them
+        pub(crate) fn prerequisite_check_weight() -> Weight {
+            T::WeightInfo::check_prerequisites(T::MaxCertificatesPerAccount::get())
+        }
+        
+        /// Check that an account holds a valid certificate of every type a rule requires
+        pub(crate) fn prerequisites_met(rule: &PrerequisiteRule<T>, account: &T::AccountId) -> bool {
+            let held: Vec<T::CertificateTypeId> = <AccountCertificates<T>>::get(account)
+                .iter()
+                .filter_map(|id| <Certificates<T>>::get(id))
+                .filter(Self::is_in_force)
+                .map(|cert| cert.cert_type)
+                .collect();
+            rule.certificate_types.iter().all(|cert_type| held.contains(cert_type))
+        }
+        
+        /// Emit `CertificateExpired` for indexed certificates in buckets ending up to `now`
+        ///
+        /// Visiting an index bucket and expiring a certificate each use one unit of
+        /// `MaxExpiriesPerBlock`; whatever is left over is picked up in the next block. Nothing is
+        /// processed until the time is known.
+        pub(crate) fn process_expiries(now: Moment) -> Weight {
+            if now.is_zero() {
+                return T::WeightInfo::process_expiries(0, 0);
+            }
+            
+            let mut budget = T::MaxExpiriesPerBlock::get() as usize;
+            let mut bucket = <ExpiryCursor<T>>::get().unwrap_or_else(|| Self::expiry_bucket(now));
+            let mut buckets: u32 = 0;
+            let mut expiries: u32 = 0;
+            
+            while bucket <= now && budget > 0 {
+                budget -= 1;
+                buckets += 1;
+                
+                let mut expired: Vec<T::CertificateId> =
+                    <ExpiryIndex<T>>::iter_key_prefix(bucket).take(budget + 1).collect();
+                let bucket_done = expired.len() <= budget;
+                expired.truncate(budget);
+                budget -= expired.len();
+                
+                for id in expired {
+                    <ExpiryIndex<T>>::remove(bucket, id);
+                    if let Some(cert) = <Certificates<T>>::get(id) {
+                        Self::deposit_event(Event::CertificateExpired { id, owner: cert.owner });
+                    }
+                    expiries += 1;
+                }
+                
+                if !bucket_done {
+                    break;
+                }
+                bucket = bucket.saturating_add(T::ExpiryGranularity::get());
+            }
+            
+            <ExpiryCursor<T>>::put(bucket);
+            
+            T::WeightInfo::process_expiries(buckets, expiries)
+        }
+        
+        /// Prune claimed voucher nonces in buckets ending up to `now`
+        ///
+        /// A nonce is only kept while its voucher can still be claimed; once the bucket its
+        /// validity ends in has ended, `claim_cert` refuses the voucher as expired anyway. Budgeted
+        /// like `process_expiries`, with one unit of `MaxExpiriesPerBlock` per bucket and per nonce.
+        pub(crate) fn process_nonce_expiries(now: Moment) -> Weight {
+            if now.is_zero() {
+                return T::WeightInfo::process_nonce_expiries(0, 0);
+            }
+            
+            let mut budget = T::MaxExpiriesPerBlock::get() as usize;
+            let mut bucket = <NonceCursor<T>>::get().unwrap_or_else(|| Self::expiry_bucket(now));
+            let mut buckets: u32 = 0;
+            let mut pruned: u32 = 0;
+            
+            while bucket <= now && budget > 0 {
+                budget -= 1;
+                buckets += 1;
+                
+                let mut expired: Vec<(T::AccountId, u64)> =
+                    <NonceExpiries<T>>::iter_key_prefix(bucket).take(budget + 1).collect();
+                let bucket_done = expired.len() <= budget;
+                expired.truncate(budget);
+                budget -= expired.len();
+                
+                for (issuer, nonce) in expired {
+                    <NonceExpiries<T>>::remove(bucket, (&issuer, nonce));
+                    <UsedNonces<T>>::remove(&issuer, nonce);
+                    pruned += 1;
+                }
+                
+                if !bucket_done {
+                    break;
+                }
+                bucket = bucket.saturating_add(T::ExpiryGranularity::get());
+            }
+            
+            <NonceCursor<T>>::put(bucket);
+            
+            T::WeightInfo::process_nonce_expiries(buckets, pruned)
+        }
+        
+        /// Apply a regulator action to up to `MaxCascadePerBlock` more certificates of an issuer
+        ///
+        /// One issuer's cascade is processed at a time, resuming after the last certificate it
+        /// processed. Certificates issued meanwhile are picked up if they come later in storage
+        /// order; the issuer cannot issue while it is suspended.
+        pub(crate) fn process_cascade() -> Weight {
+            let (issuer, mut cascade) = match <Cascades<T>>::iter().next() {
+                Some(entry) => entry,
+                None => return T::WeightInfo::process_cascade(0),
+            };
+            
+            let budget = T::MaxCascadePerBlock::get() as usize;
+            let ids = match cascade.last {
+                Some(last) => <IssuerCertificates<T>>::iter_key_prefix_from(
+                    &issuer,
+                    <IssuerCertificates<T>>::hashed_key_for(&issuer, last),
+                ),
+                None => <IssuerCertificates<T>>::iter_key_prefix(&issuer),
+            };
+            let mut ids: Vec<T::CertificateId> = ids.take(budget + 1).collect();
+            let done = ids.len() <= budget;
+            ids.truncate(budget);
+            
+            for id in ids.iter() {
+                Self::cascade_certificate(*id, cascade.action);
+            }
+            
+            if done {
+                <Cascades<T>>::remove(&issuer);
+                Self::deposit_event(Event::CascadeCompleted { issuer });
+            } else {
+                cascade.last = ids.last().copied();
+                <Cascades<T>>::insert(&issuer, cascade);
+            }
+            
+            T::WeightInfo::process_cascade(ids.len() as u32)
+        }
+        
+        /// Apply a regulator action to one certificate
+        ///
+        /// Pending offers are withdrawn; certificates already revoked or superseded are left alone.
+        fn cascade_certificate(cert_id: T::CertificateId, action: CascadeAction) {
+            let mut cert = match <Certificates<T>>::get(cert_id) {
+                Some(cert) => cert,
+                None => return,
+            };
+            
+            match (cert.status, action) {
+                (CertificateStatus::Pending, _) => Self::withdraw_offer(cert),
+                (CertificateStatus::Active, CascadeAction::Suspend) => {
+                    cert.status = CertificateStatus::ForceSuspended;
+                    <Certificates<T>>::insert(cert_id, cert);
+                    Self::deposit_event(Event::CertificateForceSuspended { id: cert_id });
+                }
+                (
+                    CertificateStatus::Active | CertificateStatus::Suspended | CertificateStatus::ForceSuspended,
+                    CascadeAction::Revoke(reason),
+                ) => {
+                    let revoked_at = Self::revoke(&mut cert, reason, None);
+                    <Certificates<T>>::insert(cert_id, cert);
+                    Self::deposit_event(Event::CertificateForceRevoked {
+                        id: cert_id,
+                        reason,
+                        note_hash: None,
+                        revoked_at,
+                    });
+                }
+                _ => {}
+            }
+        }
+        
+        /// Get the message an issuer signs to make out a voucher: the SCALE encoding of the chain's
+        /// genesis hash and the voucher, so a voucher cannot be claimed on another chain
+        pub fn voucher_payload(voucher: &Voucher<T>) -> Vec<u8> {
+            (<frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero()), voucher).encode()
+        }
+        
+        /// Get the current time in milliseconds since the Unix epoch
+        pub(crate) fn now() -> Moment {
+            T::UnixTime::now().as_millis().saturated_into()
+        }
+        
+        /// Get the end of the expiry index bucket a time falls in
+        pub(crate) fn expiry_bucket(time: Moment) -> Moment {
+            let granularity = T::ExpiryGranularity::get();
+            time.saturating_add(granularity.saturating_sub(1)) / granularity * granularity
+        }
+        
         /// Get all certificates for an account
         pub fn get_account_certificates(account: &T::AccountId) -> Vec<Certificate<T>> {
             <AccountCertificates<T>>::get(account)
@@ -213,14 +2885,327 @@ pub mod pallet {
                 .collect()
         }
         
-        /// Check if a certificate is valid
+        /// Get a page of the certificates issued by an issuer
+        ///
+        /// Pages follow storage order, not certificate ID order. Certificates burned by their
+        /// holders are listed by their tombstones. Pass the returned cursor to get the next page;
+        /// `None` means there are no more certificates.
+        pub fn get_issuer_certificates(
+            issuer: &T::AccountId,
+            cursor: Option<T::CertificateId>,
+            limit: u32,
+        ) -> (Vec<IssuedCertificate<T>>, Option<T::CertificateId>) {
+            let limit = limit.min(MAX_ISSUER_PAGE_SIZE) as usize;
+            
+            let ids = match cursor {
+                Some(cursor) => <IssuerCertificates<T>>::iter_key_prefix_from(
+                    issuer,
+                    <IssuerCertificates<T>>::hashed_key_for(issuer, cursor),
+                ),
+                None => <IssuerCertificates<T>>::iter_key_prefix(issuer),
+            };
+            let mut ids: Vec<T::CertificateId> = ids.take(limit + 1).collect();
+            
+            let next_cursor = if ids.len() > limit {
+                ids.truncate(limit);
+                ids.last().copied()
+            } else {
+                None
+            };
+            
+            let certs = ids
+                .into_iter()
+                .filter_map(|id| match <Certificates<T>>::get(id) {
+                    Some(cert) => Some(IssuedCertificate::Stored(cert)),
+                    None => <Tombstones<T>>::get(id).map(|tombstone| IssuedCertificate::Burned { id, tombstone }),
+                })
+                .collect();
+            (certs, next_cursor)
+        }
+        
+        /// Get the renewal chain containing a certificate, from the original to the latest renewal
+        pub fn get_renewal_chain(cert_id: T::CertificateId) -> Vec<Certificate<T>> {
+            // Walk back to the earliest certificate still stored
+            let mut first = cert_id;
+            while let Some(previous) = <Certificates<T>>::get(first).and_then(|cert| cert.supersedes) {
+                if !<Certificates<T>>::contains_key(previous) {
+                    break;
+                }
+                first = previous;
+            }
+            
+            // Walk forward through the renewals
+            let mut chain = Vec::new();
+            let mut next = Some(first);
+            while let Some(cert) = next.and_then(<Certificates<T>>::get) {
+                next = cert.superseded_by;
+                chain.push(cert);
+            }
+            chain
+        }
+        
+        /// Get the key an issuer signed with at a block, if it was registered by then
+        ///
+        /// Keys dropped from a full history are reported as unknown.
+        pub fn issuer_key_at(issuer: &T::AccountId, at: T::BlockNumber) -> Option<T::AccountId> {
+            let history = <IssuerKeys<T>>::get(issuer);
+            if history.is_empty() {
+                let info = <Issuers<T>>::get(issuer)?;
+                return (at >= info.registered_at).then(|| issuer.clone());
+            }
+            history
+                .into_iter()
+                .find(|entry| at >= entry.valid_from && entry.valid_until.map_or(true, |until| at < until))
+                .map(|entry| entry.key)
+        }
+        
+        /// Get the certificates offered to an account that it has not accepted yet
+        pub fn get_pending_offers(account: &T::AccountId) -> Vec<Certificate<T>> {
+            <PendingOffers<T>>::iter_key_prefix(account)
+                .filter_map(<Certificates<T>>::get)
+                .collect()
+        }
+        
+        /// Get the endorsements recorded against a certificate, oldest first
+        pub fn get_endorsements(cert_id: T::CertificateId) -> Vec<Endorsement<T>> {
+            <Endorsements<T>>::get(cert_id).into_inner()
+        }
+        
+        /// Get the lifecycle status of a certificate
+        pub fn certificate_status(cert_id: T::CertificateId) -> Option<CertificateStatus> {
+            <Certificates<T>>::get(cert_id).map(|cert| cert.status)
+        }
+        
+        /// Check that a metadata document is the one a certificate was issued with
+        ///
+        /// Burned certificates are checked against the hash kept in their tombstone.
+        pub fn verify_metadata(cert_id: T::CertificateId, document: &[u8]) -> bool {
+            match <Certificates<T>>::get(cert_id).map(|cert| cert.content) {
+                Some(CertificateContent::Inline(metadata)) => metadata[..] == document[..],
+                Some(CertificateContent::Hashed { hash, .. }) => T::Hashing::hash(document) == hash,
+                None => <Tombstones<T>>::get(cert_id)
+                    .map_or(false, |tombstone| T::Hashing::hash(document) == tombstone.content_hash),
+            }
+        }
+        
+        /// Check if a certificate is valid (active and not expired)
         pub fn is_certificate_valid(cert_id: T::CertificateId) -> bool {
             if let Some(cert) = <Certificates<T>>::get(cert_id) {
-                let current_block = <frame_system::Pallet<T>>::block_number();
-                !cert.revoked && (cert.expires_at == T::BlockNumber::from(0) || cert.expires_at > current_block)
+                Self::is_in_force(&cert)
             } else {
                 false
             }
         }
+        
+        /// Check if a certificate is active, not expired and, if its type requires it, issued by an
+        /// accredited issuer
+        fn is_in_force(cert: &Certificate<T>) -> bool {
+            cert.status == CertificateStatus::Active
+                && (cert.expires_at.is_zero() || cert.expires_at > Self::now())
+                && (!<AccreditationRequired<T>>::contains_key(cert.cert_type)
+                    || <CertificateAccreditations<T>>::contains_key(cert.id))
+        }
+        
+        /// Get the chain of trust behind a certificate: its issuer and the accreditor that had
+        /// accredited the issuer for the certificate's type when it was issued
+        ///
+        /// Returns `None` if the certificate does not exist.
+        pub fn get_trust_chain(cert_id: T::CertificateId) -> Option<TrustChain<T::AccountId>> {
+            let cert = <Certificates<T>>::get(cert_id)?;
+            let accreditation = <CertificateAccreditations<T>>::get(cert_id);
+            
+            Some(TrustChain {
+                issuer_active: Self::ensure_active_issuer(&cert.issuer).is_ok(),
+                issuer: cert.issuer,
+                accreditor_registered: accreditation
+                    .as_ref()
+                    .map_or(false, |accreditation| <Accreditors<T>>::contains_key(&accreditation.accreditor)),
+                accreditation_period: accreditation
+                    .as_ref()
+                    .map(|accreditation| (accreditation.granted_at, accreditation.expires_at)),
+                accreditor: accreditation.map(|accreditation| accreditation.accreditor),
+                accreditation_required: <AccreditationRequired<T>>::contains_key(cert.cert_type),
+            })
+        }
+        
+        /// Check if a certificate is valid and its holder still holds a valid certificate of
+        /// every prerequisite type
+        ///
+        /// Prerequisites are checked one level deep: a prerequisite certificate only needs to be
+        /// valid, not effective itself.
+        pub fn is_certificate_effective(cert_id: T::CertificateId) -> bool {
+            match <Certificates<T>>::get(cert_id) {
+                Some(cert) if Self::is_in_force(&cert) => <Prerequisites<T>>::get(cert.cert_type)
+                    .map_or(true, |rule| Self::prerequisites_met(&rule, &cert.owner)),
+                _ => false,
+            }
+        }
+        
+        /// Check an account against the requirements of a site profile
+        ///
+        /// A requirement is met by an effective certificate of the required type; of several, the
+        /// one with the most validity left counts. Returns `None` if the site profile does not
+        /// exist.
+        pub fn check_compliance(
+            account: &T::AccountId,
+            site_id: T::SiteId,
+        ) -> Option<ComplianceReport<T::CertificateTypeId, T::CertificateId>> {
+            let profile = <SiteProfiles<T>>::get(site_id)?;
+            let now = Self::now();
+            let certs: Vec<Certificate<T>> = Self::get_account_certificates(account)
+                .into_iter()
+                .filter(|cert| Self::is_certificate_effective(cert.id))
+                .collect();
+            
+            let mut missing = Vec::new();
+            let mut expiring = Vec::new();
+            for requirement in profile.requirements.iter() {
+                // Certificates without expiry have the most validity left
+                let best = certs
+                    .iter()
+                    .filter(|cert| cert.cert_type == requirement.cert_type)
+                    .max_by_key(|cert| (cert.expires_at.is_zero(), cert.expires_at));
+                
+                match best {
+                    None => missing.push(requirement.cert_type),
+                    Some(cert)
+                        if !requirement.min_remaining_validity.is_zero()
+                            && !cert.expires_at.is_zero()
+                            && cert.expires_at < now.saturating_add(requirement.min_remaining_validity) =>
+                    {
+                        expiring.push((requirement.cert_type, cert.id))
+                    }
+                    Some(_) => {}
+                }
+            }
+            
+            Some(ComplianceReport {
+                compliant: missing.is_empty() && expiring.is_empty(),
+                missing,
+                expiring,
+            })
+        }
+        
+        /// Check the storage invariants of the pallet
+        ///
+        /// * Every ID listed for an account or an issuer refers to a stored certificate of that
+        ///   account or issuer.
+        /// * Every expiry index entry refers to a certificate in force expiring in that bucket.
+        /// * Pending certificates are exactly the pending offers of their holders.
+        /// * No burned certificate is still stored.
+        /// * Every accreditation record and endorsement refers to a stored certificate.
+        /// * Signing keys are exactly the current keys of the issuer key histories.
+        /// * No certificate ID has been handed out beyond `CertificateCount`, and no site profile
+        ///   ID beyond `SiteCount`.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), &'static str> {
+            let count: u32 = <CertificateCount<T>>::get().into();
+            
+            for (account, ids) in <AccountCertificates<T>>::iter() {
+                for id in ids {
+                    let cert = <Certificates<T>>::get(id).ok_or("account lists a missing certificate")?;
+                    ensure!(cert.owner == account, "account lists a certificate it does not own");
+                }
+            }
+            
+            for (issuer, id, ()) in <IssuerCertificates<T>>::iter() {
+                let cert_issuer = match <Certificates<T>>::get(id) {
+                    Some(cert) => cert.issuer,
+                    None => <Tombstones<T>>::get(id).ok_or("issuer index lists a missing certificate")?.issuer,
+                };
+                ensure!(cert_issuer == issuer, "issuer index lists a certificate of another issuer");
+            }
+            
+            for (bucket, id, ()) in <ExpiryIndex<T>>::iter() {
+                let cert = <Certificates<T>>::get(id).ok_or("expiry index lists a missing certificate")?;
+                ensure!(
+                    Self::expiry_bucket(cert.expires_at) == bucket,
+                    "expiry index bucket does not match certificate expiry"
+                );
+                ensure!(
+                    matches!(
+                        cert.status,
+                        CertificateStatus::Active | CertificateStatus::Suspended | CertificateStatus::ForceSuspended
+                    ),
+                    "expiry index lists a certificate no longer in force"
+                );
+            }
+            
+            for (issuer, nonce, valid_until) in <UsedNonces<T>>::iter() {
+                ensure!(
+                    <NonceExpiries<T>>::contains_key(Self::expiry_bucket(valid_until), (&issuer, nonce)),
+                    "claimed nonce missing from the nonce expiry index"
+                );
+            }
+            
+            for (id, cert) in <Certificates<T>>::iter() {
+                ensure!(Into::<u32>::into(id) < count, "certificate ID beyond CertificateCount");
+                ensure!(
+                    (cert.status == CertificateStatus::Pending) == <PendingOffers<T>>::contains_key(&cert.owner, id),
+                    "pending certificate does not match the pending offers"
+                );
+            }
+            
+            for (key, issuer) in <SigningKeys<T>>::iter() {
+                let current = <IssuerKeys<T>>::get(&issuer)
+                    .last()
+                    .cloned()
+                    .ok_or("signing key of an issuer without key history")?;
+                ensure!(
+                    current.key == key && current.valid_until.is_none(),
+                    "signing key is not its issuer's current key"
+                );
+            }
+            
+            for (issuer, history) in <IssuerKeys<T>>::iter() {
+                ensure!(<Issuers<T>>::contains_key(&issuer), "key history of an unregistered issuer");
+                let current = history.last().ok_or("empty issuer key history")?;
+                ensure!(<SigningKeys<T>>::get(&current.key) == Some(issuer), "current issuer key is not a signing key");
+            }
+            
+            for key in <RetiredKeys<T>>::iter_keys() {
+                ensure!(!<SigningKeys<T>>::contains_key(&key), "retired key is still a signing key");
+            }
+            
+            for (delegate, issuer) in <Delegates<T>>::iter() {
+                ensure!(<Issuers<T>>::contains_key(&issuer), "delegate of an unregistered issuer");
+                ensure!(
+                    <IssuerDelegates<T>>::get(&issuer).contains(&delegate),
+                    "delegate missing from its issuer's list"
+                );
+            }
+            
+            for (issuer, delegates) in <IssuerDelegates<T>>::iter() {
+                for delegate in delegates {
+                    ensure!(
+                        <Delegates<T>>::get(&delegate) == Some(issuer.clone()),
+                        "issuer lists a delegate of another issuer"
+                    );
+                }
+            }
+            
+            for (id, tombstone) in <Tombstones<T>>::iter() {
+                ensure!(!<Certificates<T>>::contains_key(id), "burned certificate is still stored");
+                ensure!(
+                    <IssuerCertificates<T>>::contains_key(&tombstone.issuer, id),
+                    "burned certificate missing from its issuer's index"
+                );
+            }
+            
+            for id in <CertificateAccreditations<T>>::iter_keys() {
+                ensure!(<Certificates<T>>::contains_key(id), "accreditation record of a missing certificate");
+            }
+            
+            for id in <Endorsements<T>>::iter_keys() {
+                ensure!(<Certificates<T>>::contains_key(id), "endorsement of a missing certificate");
+            }
+            
+            let site_count: u32 = <SiteCount<T>>::get().into();
+            for site_id in <SiteProfiles<T>>::iter_keys() {
+                ensure!(Into::<u32>::into(site_id) < site_count, "site profile ID beyond SiteCount");
+            }
+            
+            Ok(())
+        }
     }
-} 
\ No newline at end of file
+}
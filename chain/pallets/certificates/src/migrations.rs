@@ -0,0 +1,416 @@
+//! Storage migrations for the certificates pallet.
+//!
+//! Each module migrates storage from the previous version to the version it is named after.
+//! Migrations check the on-chain storage version themselves, so they are safe to leave in a
+//! runtime's `Executive` migrations for a release or two.
+
+use super::*;
+use frame_support::{
+    pallet_prelude::*,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::traits::{SaturatedConversion, Zero};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Storage layout at version 1
+pub mod v1 {
+    use super::*;
+
+    /// Certificate as stored at version 1
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Certificate<T: Config> {
+        /// Certificate ID
+        pub id: T::CertificateId,
+        /// Certificate owner
+        pub owner: T::AccountId,
+        /// Certificate issuer
+        pub issuer: T::AccountId,
+        /// Certificate metadata (stored as JSON string)
+        pub metadata: BoundedVec<u8, T::MaxMetadataLength>,
+        /// Certificate issuance time
+        pub issued_at: T::BlockNumber,
+        /// Certificate revocation status
+        pub revoked: bool,
+        /// Certificate expiry time (0 if no expiry)
+        pub expires_at: T::BlockNumber,
+    }
+
+    /// Certificate lists as stored at version 1, bounded by `MaxMetadataLength`
+    #[frame_support::storage_alias]
+    pub type AccountCertificates<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Vec<<T as Config>::CertificateId>,
+    >;
+}
+
+/// Storage layout at version 2
+pub mod v2 {
+    use super::*;
+
+    /// Certificate as stored at version 2, expiring at a block
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Certificate<T: Config> {
+        /// Certificate ID
+        pub id: T::CertificateId,
+        /// Certificate owner
+        pub owner: T::AccountId,
+        /// Certificate issuer
+        pub issuer: T::AccountId,
+        /// Account that signed the issuance
+        pub issued_by: T::AccountId,
+        /// Certificate type
+        pub cert_type: T::CertificateTypeId,
+        /// Certificate metadata, inline or as a hash of an off-chain document
+        pub content: CertificateContent<T>,
+        /// Storage deposit held from the issuer
+        pub deposit: BalanceOf<T>,
+        /// Certificate issuance time
+        pub issued_at: T::BlockNumber,
+        /// Certificate lifecycle status
+        pub status: CertificateStatus,
+        /// Revocation record (set once revoked)
+        pub revocation: Option<RevocationInfo<T>>,
+        /// Certificate expiry block (0 if no expiry)
+        pub expires_at: T::BlockNumber,
+        /// Certificate this one renews, if any
+        pub supersedes: Option<T::CertificateId>,
+        /// Certificate that renewed this one, if any
+        pub superseded_by: Option<T::CertificateId>,
+    }
+
+    /// Certificate type registry entry as stored at version 2, with a validity in blocks
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CertificateTypeInfo<T: Config> {
+        /// Canonical display name
+        pub name: BoundedVec<u8, T::MaxTypeNameLength>,
+        /// Validity period in blocks applied when no expiry is given at issuance (0 if no expiry)
+        pub default_validity: T::BlockNumber,
+        /// Top-level fields the certificate metadata JSON object must carry
+        pub required_fields: BoundedVec<BoundedVec<u8, T::MaxTypeNameLength>, T::MaxRequiredFields>,
+    }
+
+    /// Site requirement as stored at version 2, with a remaining validity in blocks
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SiteRequirement<T: Config> {
+        /// Required certificate type
+        pub cert_type: T::CertificateTypeId,
+        /// Validity the certificate must have left, in blocks
+        pub min_remaining_validity: T::BlockNumber,
+    }
+
+    /// Site profile as stored at version 2
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SiteProfile<T: Config> {
+        /// Account managing the profile
+        pub owner: T::AccountId,
+        /// Site name
+        pub name: BoundedVec<u8, T::MaxSiteNameLength>,
+        /// Certificates a worker needs for the site
+        pub requirements: BoundedVec<SiteRequirement<T>, T::MaxSiteRequirements>,
+    }
+
+    /// Certificates as stored at version 2
+    #[frame_support::storage_alias]
+    pub type Certificates<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::CertificateId, Certificate<T>>;
+
+    /// Certificates in force indexed by the block at which they expire
+    #[frame_support::storage_alias]
+    pub type ExpiryIndex<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Twox64Concat,
+        <T as frame_system::Config>::BlockNumber,
+        Blake2_128Concat,
+        <T as Config>::CertificateId,
+        (),
+    >;
+
+    /// Migrate storage from version 1 to version 2.
+    ///
+    /// Version 2 was never released on its own: the layout above grew in place while it was
+    /// unreleased, and it is only ever run as the first step from version 1 to version 3.
+    ///
+    /// * `Certificates` gain a type, a lifecycle status and renewal links, and keep their
+    ///   metadata as inline content. Version 1 had no certificate types, so every existing
+    ///   certificate is given `LegacyType`, which should be registered before or alongside the
+    ///   upgrade. Revoked certificates carry no revocation record because version 1 did not
+    ///   keep one, and the issuer is recorded as the signer because there were no delegates.
+    ///   No deposit is held for existing certificates.
+    /// * `AccountCertificates` are re-bounded by `MaxCertificatesPerAccount` instead of
+    ///   `MaxMetadataLength`. `pre_upgrade` fails if a list is longer than the new bound, so
+    ///   raise the bound or prune those lists first. Should the upgrade run anyway, such lists
+    ///   drop revoked certificates first, then the oldest ones, and every dropped ID is logged;
+    ///   the certificates themselves stay stored.
+    /// * `IssuerCertificates` and `ExpiryIndex` are built for the existing certificates.
+    pub struct MigrateToV2<T, LegacyType>(PhantomData<(T, LegacyType)>);
+
+    impl<T: Config, LegacyType: Get<T::CertificateTypeId>> OnRuntimeUpgrade for MigrateToV2<T, LegacyType> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 2 {
+                log::info!(target: "runtime::certificates", "MigrateToV2 skipped, storage version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let cert_type = LegacyType::get();
+            let mut reads: u64 = 2;
+            let mut writes: u64 = 1;
+
+            v2::Certificates::<T>::translate::<v1::Certificate<T>, _>(|id, old| {
+                reads += 1;
+                writes += 2;
+
+                let status = if old.revoked { CertificateStatus::Revoked } else { CertificateStatus::Active };
+
+                IssuerCertificates::<T>::insert(&old.issuer, id, ());
+                if status == CertificateStatus::Active && old.expires_at > now {
+                    v2::ExpiryIndex::<T>::insert(old.expires_at, id, ());
+                    writes += 1;
+                }
+
+                Some(v2::Certificate {
+                    id: old.id,
+                    owner: old.owner,
+                    issuer: old.issuer.clone(),
+                    issued_by: old.issuer,
+                    cert_type,
+                    content: CertificateContent::Inline(old.metadata),
+                    deposit: Zero::zero(),
+                    issued_at: old.issued_at,
+                    status,
+                    revocation: None,
+                    expires_at: old.expires_at,
+                    supersedes: None,
+                    superseded_by: None,
+                })
+            });
+
+            let max = T::MaxCertificatesPerAccount::get() as usize;
+
+            AccountCertificates::<T>::translate::<Vec<T::CertificateId>, _>(|account, mut ids| {
+                reads += 1;
+                writes += 1;
+
+                if ids.len() > max {
+                    reads += ids.len() as u64;
+                    let (mut kept, mut dropped): (Vec<_>, Vec<_>) = ids.into_iter().partition(|id| {
+                        v2::Certificates::<T>::get(id).map_or(false, |cert| cert.status == CertificateStatus::Active)
+                    });
+                    if kept.len() > max {
+                        dropped.extend(kept.drain(..kept.len() - max));
+                    }
+                    log::warn!(
+                        target: "runtime::certificates",
+                        "Dropping certificates {:?} from the list of {:?} to fit {} entries",
+                        dropped,
+                        account,
+                        max,
+                    );
+                    ids = kept;
+                }
+                Some(BoundedVec::truncate_from(ids))
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            log::info!(target: "runtime::certificates", "MigrateToV2 done after {} reads and {} writes", reads, writes);
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let max = T::MaxCertificatesPerAccount::get() as usize;
+            if Pallet::<T>::on_chain_storage_version() < 2 {
+                ensure!(
+                    v1::AccountCertificates::<T>::iter_values().all(|ids| ids.len() <= max),
+                    "an account lists more certificates than MaxCertificatesPerAccount"
+                );
+            }
+
+            let certificates = Certificates::<T>::iter_keys().count() as u32;
+            let accounts = AccountCertificates::<T>::iter_keys().count() as u32;
+            Ok((certificates, accounts).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let (certificates, accounts): (u32, u32) =
+                Decode::decode(&mut &state[..]).map_err(|_| "pre_upgrade state does not decode")?;
+
+            ensure!(Pallet::<T>::on_chain_storage_version() >= 2, "storage version not updated");
+            ensure!(
+                v2::Certificates::<T>::iter_values().count() as u32 == certificates,
+                "certificates lost or not decodable after migration"
+            );
+            ensure!(
+                AccountCertificates::<T>::iter_values().count() as u32 == accounts,
+                "account lists lost or not decodable after migration"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use super::*;
+
+    /// Migrate storage from version 2 to version 3.
+    ///
+    /// * Certificate expiries, certificate type default validities and site requirement
+    ///   validities move from blocks to milliseconds. Expiries are converted relative to the
+    ///   current block and time assuming `MillisecsPerBlock` per block; expiries in the past stay
+    ///   in the past.
+    /// * `ExpiryIndex` is rebuilt by time and `ExpiryCursor` restarts at the current time, so
+    ///   expiries the old index had not reported yet are not reported.
+    /// * No deposit is held for existing site profiles.
+    pub struct MigrateToV3<T, MillisecsPerBlock>(PhantomData<(T, MillisecsPerBlock)>);
+
+    impl<T: Config, MillisecsPerBlock: Get<Moment>> MigrateToV3<T, MillisecsPerBlock> {
+        /// Convert a block-number expiry to a time, keeping 0 as no expiry
+        fn expiry_time(expires_at: T::BlockNumber, now_block: u64, now: Moment) -> Moment {
+            let expires_at: u64 = expires_at.saturated_into();
+            if expires_at.is_zero() {
+                return Zero::zero();
+            }
+            let per_block = MillisecsPerBlock::get();
+            if expires_at >= now_block {
+                now.saturating_add((expires_at - now_block).saturating_mul(per_block))
+            } else {
+                now.saturating_sub((now_block - expires_at).saturating_mul(per_block)).max(1)
+            }
+        }
+
+        /// Convert a duration in blocks to milliseconds
+        fn duration(blocks: T::BlockNumber) -> Moment {
+            blocks.saturated_into::<u64>().saturating_mul(MillisecsPerBlock::get())
+        }
+    }
+
+    impl<T: Config, MillisecsPerBlock: Get<Moment>> OnRuntimeUpgrade for MigrateToV3<T, MillisecsPerBlock> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                log::info!(target: "runtime::certificates", "MigrateToV3 skipped, storage version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let now_block: u64 = <frame_system::Pallet<T>>::block_number().saturated_into();
+            let now = Pallet::<T>::now();
+            let mut reads: u64 = 3;
+            let mut writes: u64 = 2;
+
+            // Old and new index entries share a prefix, so the old ones go first
+            let cleared = v2::ExpiryIndex::<T>::clear(u32::MAX, None);
+            reads += cleared.loops as u64;
+            writes += cleared.unique as u64;
+
+            Certificates::<T>::translate::<v2::Certificate<T>, _>(|id, old| {
+                reads += 1;
+                writes += 1;
+
+                let expires_at = Self::expiry_time(old.expires_at, now_block, now);
+                let in_force = matches!(
+                    old.status,
+                    CertificateStatus::Active | CertificateStatus::Suspended | CertificateStatus::ForceSuspended
+                );
+                if in_force && expires_at > now {
+                    ExpiryIndex::<T>::insert(Pallet::<T>::expiry_bucket(expires_at), id, ());
+                    writes += 1;
+                }
+
+                Some(Certificate {
+                    id: old.id,
+                    owner: old.owner,
+                    issuer: old.issuer,
+                    issued_by: old.issued_by,
+                    cert_type: old.cert_type,
+                    content: old.content,
+                    deposit: old.deposit,
+                    issued_at: old.issued_at,
+                    status: old.status,
+                    revocation: old.revocation,
+                    expires_at,
+                    supersedes: old.supersedes,
+                    superseded_by: old.superseded_by,
+                })
+            });
+
+            ExpiryCursor::<T>::put(Pallet::<T>::expiry_bucket(now));
+
+            CertificateTypes::<T>::translate::<v2::CertificateTypeInfo<T>, _>(|_, old| {
+                reads += 1;
+                writes += 1;
+                Some(CertificateTypeInfo {
+                    name: old.name,
+                    default_validity: Self::duration(old.default_validity),
+                    required_fields: old.required_fields,
+                })
+            });
+
+            SiteProfiles::<T>::translate::<v2::SiteProfile<T>, _>(|_, old| {
+                reads += 1;
+                writes += 1;
+                let requirements = old
+                    .requirements
+                    .into_iter()
+                    .map(|requirement| SiteRequirement {
+                        cert_type: requirement.cert_type,
+                        min_remaining_validity: Self::duration(requirement.min_remaining_validity),
+                    })
+                    .collect::<Vec<_>>();
+                Some(SiteProfile {
+                    owner: old.owner,
+                    name: old.name,
+                    requirements: BoundedVec::truncate_from(requirements),
+                    deposit: Zero::zero(),
+                })
+            });
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+
+            log::info!(target: "runtime::certificates", "MigrateToV3 done after {} reads and {} writes", reads, writes);
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let certificates = Certificates::<T>::iter_keys().count() as u32;
+            let types = CertificateTypes::<T>::iter_keys().count() as u32;
+            let sites = SiteProfiles::<T>::iter_keys().count() as u32;
+            Ok((certificates, types, sites).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let (certificates, types, sites): (u32, u32, u32) =
+                Decode::decode(&mut &state[..]).map_err(|_| "pre_upgrade state does not decode")?;
+
+            ensure!(Pallet::<T>::on_chain_storage_version() >= 3, "storage version not updated");
+            ensure!(
+                Certificates::<T>::iter_values().count() as u32 == certificates,
+                "certificates lost or not decodable after migration"
+            );
+            ensure!(
+                CertificateTypes::<T>::iter_values().count() as u32 == types,
+                "certificate types lost or not decodable after migration"
+            );
+            ensure!(
+                SiteProfiles::<T>::iter_values().count() as u32 == sites,
+                "site profiles lost or not decodable after migration"
+            );
+
+            Pallet::<T>::do_try_state()
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate as pallet_certificates;
+use frame_support::{
+    parameter_types,
+    traits::{ConstU16, ConstU32, ConstU64, EnsureOrigin},
+};
+use frame_system::{EnsureRoot, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{
+    testing::{Header, TestSignature, UintAuthorityId},
+    traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ISSUER: u64 = 1;
+pub const OTHER_ISSUER: u64 = 2;
+pub const INSTRUCTOR: u64 = 3;
+pub const OTHER_INSTRUCTOR: u64 = 4;
+pub const NEW_KEY: u64 = 5;
+pub const OTHER_NEW_KEY: u64 = 6;
+pub const HOLDER: u64 = 10;
+pub const OTHER_HOLDER: u64 = 11;
+pub const ACCREDITOR: u64 = 30;
+pub const OTHER_ACCREDITOR: u64 = 31;
+pub const ENDORSER: u64 = 40;
+pub const OTHER_ENDORSER: u64 = 41;
+pub const SITE_OWNER: u64 = 50;
+pub const TREASURY: u64 = 99;
+
+pub const WHMIS: u32 = 1;
+pub const RIGGING: u32 = 2;
+
+/// Time between blocks, which is also the expiry index granularity, in milliseconds
+pub const MILLISECS_PER_BLOCK: u64 = 6_000;
+
+/// Default validity of the WHMIS type, in milliseconds (100 blocks)
+pub const WHMIS_VALIDITY: u64 = 100 * MILLISECS_PER_BLOCK;
+
+/// Number of blocks a certificate offer stays open
+pub const OFFER_PERIOD: u64 = 20;
+
+/// Longest a voucher may stay claimable, in milliseconds (20 blocks)
+pub const MAX_VOUCHER_VALIDITY: u64 = 20 * MILLISECS_PER_BLOCK;
+
+/// Free balance of each issuer, endorser and the site owner at genesis
+pub const INITIAL_BALANCE: u64 = 1_000;
+pub const DEPOSIT_BASE: u64 = 10;
+pub const DEPOSIT_PER_BYTE: u64 = 1;
+pub const ENDORSEMENT_DEPOSIT: u64 = 5;
+pub const SITE_PROFILE_DEPOSIT: u64 = 20;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Timestamp: pallet_timestamp,
+        Balances: pallet_balances,
+        Certificates: pallet_certificates,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<{ MILLISECS_PER_BLOCK / 2 }>;
+    type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type HoldIdentifier = ();
+    type MaxHolds = ();
+}
+
+parameter_types! {
+    /// Signed accounts accepted by `IssuerOrigin`; tests may change it with `AllowedIssuers::set`
+    pub static AllowedIssuers: Vec<u64> =
+        vec![ISSUER, OTHER_ISSUER, INSTRUCTOR, OTHER_INSTRUCTOR, NEW_KEY, OTHER_NEW_KEY];
+}
+
+/// `IssuerOrigin` accepting the signed accounts listed in `AllowedIssuers`
+pub struct EnsureAllowedIssuer;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureAllowedIssuer {
+    type Success = u64;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Signed(who) if AllowedIssuers::get().contains(&who) => Ok(who),
+            r => Err(RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Ok(RuntimeOrigin::signed(ISSUER))
+    }
+}
+
+/// Signs benchmark vouchers with a test signature from `ISSUER`
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_certificates::BenchmarkHelper<UintAuthorityId, u64, TestSignature> for MockBenchmarkHelper {
+    fn signer() -> (UintAuthorityId, u64) {
+        (UintAuthorityId(ISSUER), ISSUER)
+    }
+
+    fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+        TestSignature(signer.0, message.to_vec())
+    }
+}
+
+impl pallet_certificates::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type CertificateId = u32;
+    type CertificateTypeId = u32;
+    type SiteId = u32;
+    type Currency = Balances;
+    type CertificateDepositBase = ConstU64<DEPOSIT_BASE>;
+    type CertificateDepositPerByte = ConstU64<DEPOSIT_PER_BYTE>;
+    type EndorsementDeposit = ConstU64<ENDORSEMENT_DEPOSIT>;
+    type SiteProfileDeposit = ConstU64<SITE_PROFILE_DEPOSIT>;
+    type TreasuryAccount = ConstU64<TREASURY>;
+    type MaxMetadataLength = ConstU32<64>;
+    type MaxLocatorLength = ConstU32<64>;
+    type MaxCertificatesPerAccount = ConstU32<4>;
+    type MaxIssuerFieldLength = ConstU32<32>;
+    type MaxCertificateTypesPerIssuer = ConstU32<3>;
+    type MaxTypeNameLength = ConstU32<16>;
+    type MaxRequiredFields = ConstU32<2>;
+    type MaxPrerequisites = ConstU32<2>;
+    type MaxExpiriesPerBlock = ConstU32<3>;
+    type MaxCascadePerBlock = ConstU32<2>;
+    type MaxBatchSize = ConstU32<3>;
+    type MaxSiteNameLength = ConstU32<16>;
+    type MaxSiteRequirements = ConstU32<3>;
+    type MaxKeyHistory = ConstU32<3>;
+    type MaxDelegatesPerIssuer = ConstU32<2>;
+    type MaxEndorsementsPerCertificate = ConstU32<2>;
+    type OfferPeriod = ConstU64<OFFER_PERIOD>;
+    type UnixTime = Timestamp;
+    type ExpiryGranularity = ConstU64<MILLISECS_PER_BLOCK>;
+    type MaxVoucherValidity = ConstU64<MAX_VOUCHER_VALIDITY>;
+    type IssuerOrigin = EnsureAllowedIssuer;
+    type RegistrarOrigin = EnsureRoot<u64>;
+    type ForceOrigin = EnsureRoot<u64>;
+    type OffchainSignature = TestSignature;
+    type OffchainPublic = UintAuthorityId;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MockBenchmarkHelper;
+    type WeightInfo = ();
+}
+
+/// Build genesis storage with the WHMIS and Rigging types registered, `ISSUER` allowed to
+/// issue both and the issuers, endorsers and site owner funded, at block 1 so that events are recorded
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![
+            (ISSUER, INITIAL_BALANCE),
+            (OTHER_ISSUER, INITIAL_BALANCE),
+            (ENDORSER, INITIAL_BALANCE),
+            (OTHER_ENDORSER, INITIAL_BALANCE),
+            (SITE_OWNER, INITIAL_BALANCE),
+        ],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(at_block(1));
+
+        Certificates::register_certificate_type(
+            RuntimeOrigin::root(),
+            WHMIS,
+            b"WHMIS 2015".to_vec(),
+            WHMIS_VALIDITY,
+            vec![b"name".to_vec()],
+        )
+        .unwrap();
+        Certificates::register_certificate_type(RuntimeOrigin::root(), RIGGING, b"Rigging".to_vec(), 0, vec![])
+            .unwrap();
+        Certificates::register_issuer(
+            RuntimeOrigin::root(),
+            ISSUER,
+            b"Acme Safety Training".to_vec(),
+            b"ACC-001".to_vec(),
+            b"https://acme.example".to_vec(),
+            vec![WHMIS, RIGGING],
+        )
+        .unwrap();
+    });
+    ext
+}
+
+/// Run a test and check the pallet's storage invariants afterwards
+pub fn build_and_execute(test: impl FnOnce()) {
+    new_test_ext().execute_with(|| {
+        test();
+        Certificates::do_try_state().expect("storage invariants hold");
+    });
+}
+
+/// Advance to block `n`, setting the time and running `on_initialize` for every block on the way
+pub fn run_to_block(n: u64) {
+    use frame_support::traits::Hooks;
+
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        Timestamp::set_timestamp(at_block(System::block_number()));
+        Certificates::on_initialize(System::block_number());
+    }
+}
+
+/// Time at which block `n` is produced, in milliseconds
+pub fn at_block(n: u64) -> u64 {
+    n * MILLISECS_PER_BLOCK
+}
+
+/// Sign a voucher as `signer`
+pub fn sign_voucher(signer: u64, voucher: &pallet_certificates::Voucher<Test>) -> TestSignature {
+    TestSignature(signer, Certificates::voucher_payload(voucher))
+}
+
+/// JSON metadata carrying the `name` field required by WHMIS
+pub fn metadata(name: &str) -> Vec<u8> {
+    format!("{{\"name\":\"{}\"}}", name).into_bytes()
+}
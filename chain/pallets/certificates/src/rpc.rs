@@ -11,8 +11,20 @@ use std::sync::Arc;
 
 use sp_std::vec::Vec;
 
+use crate::CertificateStatus;
+
 #[rpc(client, server)]
-pub trait CertificatesApi<BlockHash, AccountId, CertificateId, Certificate> {
+pub trait CertificatesApi<
+    BlockHash,
+    AccountId,
+    CertificateId,
+    Certificate,
+    IssuedCertificate,
+    SiteId,
+    ComplianceReport,
+    TrustChain,
+    Endorsement,
+> {
     #[method(name = "certificates_getAccountCertificates")]
     fn get_account_certificates(
         &self,
@@ -20,12 +32,79 @@ pub trait CertificatesApi<BlockHash, AccountId, CertificateId, Certificate> {
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<Certificate>>;
 
+    #[method(name = "certificates_getPendingOffers")]
+    fn get_pending_offers(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<Certificate>>;
+
     #[method(name = "certificates_isCertificateValid")]
     fn is_certificate_valid(
         &self,
         cert_id: CertificateId,
         at: Option<BlockHash>,
     ) -> RpcResult<bool>;
+
+    #[method(name = "certificates_isCertificateEffective")]
+    fn is_certificate_effective(
+        &self,
+        cert_id: CertificateId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+
+    #[method(name = "certificates_getCertificateStatus")]
+    fn get_certificate_status(
+        &self,
+        cert_id: CertificateId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<CertificateStatus>>;
+
+    #[method(name = "certificates_getRenewalChain")]
+    fn get_renewal_chain(
+        &self,
+        cert_id: CertificateId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<Certificate>>;
+
+    #[method(name = "certificates_getIssuerCertificates")]
+    fn get_issuer_certificates(
+        &self,
+        issuer: AccountId,
+        cursor: Option<CertificateId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(Vec<IssuedCertificate>, Option<CertificateId>)>;
+
+    #[method(name = "certificates_verifyMetadata")]
+    fn verify_metadata(
+        &self,
+        cert_id: CertificateId,
+        document: Vec<u8>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+
+    #[method(name = "certificates_checkCompliance")]
+    fn check_compliance(
+        &self,
+        account: AccountId,
+        site_id: SiteId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<ComplianceReport>>;
+
+    #[method(name = "certificates_getTrustChain")]
+    fn get_trust_chain(
+        &self,
+        cert_id: CertificateId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<TrustChain>>;
+
+    #[method(name = "certificates_getEndorsements")]
+    fn get_endorsements(
+        &self,
+        cert_id: CertificateId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<Endorsement>>;
 }
 
 /// A struct that implements the `CertificatesApi`.
@@ -58,16 +137,50 @@ impl From<Error> for i32 {
     }
 }
 
-impl<C, Block, AccountId, CertificateId, Certificate>
-    CertificatesApiServer<<Block as BlockT>::Hash, AccountId, CertificateId, Certificate>
-    for Certificates<C, Block>
+impl<
+        C,
+        Block,
+        AccountId,
+        CertificateId,
+        Certificate,
+        IssuedCertificate,
+        SiteId,
+        ComplianceReport,
+        TrustChain,
+        Endorsement,
+    > CertificatesApiServer<
+        <Block as BlockT>::Hash,
+        AccountId,
+        CertificateId,
+        Certificate,
+        IssuedCertificate,
+        SiteId,
+        ComplianceReport,
+        TrustChain,
+        Endorsement,
+    > for Certificates<C, Block>
 where
     Block: BlockT,
     C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
-    C::Api: CertificatesRuntimeApi<Block, AccountId, CertificateId, Certificate>,
+    C::Api: CertificatesRuntimeApi<
+        Block,
+        AccountId,
+        CertificateId,
+        Certificate,
+        IssuedCertificate,
+        SiteId,
+        ComplianceReport,
+        TrustChain,
+        Endorsement,
+    >,
     AccountId: Codec + Send + Sync + 'static,
     CertificateId: Codec + Send + Sync + 'static,
     Certificate: Codec + Send + Sync + 'static,
+    IssuedCertificate: Codec + Send + Sync + 'static,
+    SiteId: Codec + Send + Sync + 'static,
+    ComplianceReport: Codec + Send + Sync + 'static,
+    TrustChain: Codec + Send + Sync + 'static,
+    Endorsement: Codec + Send + Sync + 'static,
 {
     fn get_account_certificates(
         &self,
@@ -88,6 +201,25 @@ where
             })
     }
 
+    fn get_pending_offers(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<Certificate>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_pending_offers(&at, account)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to get pending certificate offers.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
     fn is_certificate_valid(
         &self,
         cert_id: CertificateId,
@@ -106,10 +238,189 @@ where
                 .into()
             })
     }
+
+    fn is_certificate_effective(
+        &self,
+        cert_id: CertificateId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<bool> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.is_certificate_effective(&at, cert_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to check certificate prerequisites.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn get_certificate_status(
+        &self,
+        cert_id: CertificateId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<CertificateStatus>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_certificate_status(&at, cert_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to get certificate status.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn get_renewal_chain(
+        &self,
+        cert_id: CertificateId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<Certificate>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_renewal_chain(&at, cert_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to get certificate renewal chain.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn get_issuer_certificates(
+        &self,
+        issuer: AccountId,
+        cursor: Option<CertificateId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(Vec<IssuedCertificate>, Option<CertificateId>)> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_issuer_certificates(&at, issuer, cursor, limit)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to get issuer certificates.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn verify_metadata(
+        &self,
+        cert_id: CertificateId,
+        document: Vec<u8>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<bool> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.verify_metadata(&at, cert_id, document)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to verify certificate metadata.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn check_compliance(
+        &self,
+        account: AccountId,
+        site_id: SiteId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<ComplianceReport>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.check_compliance(&at, account, site_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to check site compliance.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn get_trust_chain(
+        &self,
+        cert_id: CertificateId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<TrustChain>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_trust_chain(&at, cert_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to get certificate trust chain.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn get_endorsements(
+        &self,
+        cert_id: CertificateId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<Endorsement>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_endorsements(&at, cert_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to get certificate endorsements.",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
 }
 
 #[sp_api::api]
-pub trait CertificatesRuntimeApi<Block: BlockT, AccountId, CertificateId, Certificate> {
+pub trait CertificatesRuntimeApi<
+    Block: BlockT,
+    AccountId,
+    CertificateId,
+    Certificate,
+    IssuedCertificate,
+    SiteId,
+    ComplianceReport,
+    TrustChain,
+    Endorsement,
+> {
     fn get_account_certificates(account: AccountId) -> Vec<Certificate>;
+    fn get_pending_offers(account: AccountId) -> Vec<Certificate>;
     fn is_certificate_valid(cert_id: CertificateId) -> bool;
+    fn is_certificate_effective(cert_id: CertificateId) -> bool;
+    fn get_certificate_status(cert_id: CertificateId) -> Option<CertificateStatus>;
+    fn get_renewal_chain(cert_id: CertificateId) -> Vec<Certificate>;
+    fn get_issuer_certificates(
+        issuer: AccountId,
+        cursor: Option<CertificateId>,
+        limit: u32,
+    ) -> (Vec<IssuedCertificate>, Option<CertificateId>);
+    fn verify_metadata(cert_id: CertificateId, document: Vec<u8>) -> bool;
+    fn check_compliance(account: AccountId, site_id: SiteId) -> Option<ComplianceReport>;
+    fn get_trust_chain(cert_id: CertificateId) -> Option<TrustChain>;
+    fn get_endorsements(cert_id: CertificateId) -> Vec<Endorsement>;
 } 
\ No newline at end of file
@@ -0,0 +1,3283 @@
+use crate::{
+    mock::*, AccountCertificates, CertificateContent, CertificateCount, CertificateStatus, Error, Event,
+    ExpiryCursor, ExpiryIndex, IssuerKey, IssuerStatus, RevocationInfo, RevocationReason,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, Hooks},
+    BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash},
+    DispatchError,
+};
+
+fn issue(holder: u64, expires_at: Option<u64>) -> u32 {
+    let id = Certificates::certificate_count();
+    assert_ok!(Certificates::issue_cert(
+        RuntimeOrigin::signed(ISSUER),
+        holder,
+        WHMIS,
+        metadata("Jane Doe"),
+        expires_at,
+    ));
+    id
+}
+
+mod issue_cert {
+    use super::*;
+
+    #[test]
+    fn works() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                WHMIS,
+                metadata("Jane Doe"),
+                Some(at_block(50)),
+            ));
+
+            let cert = Certificates::certificates(0).unwrap();
+            assert_eq!(cert.owner, HOLDER);
+            assert_eq!(cert.issuer, ISSUER);
+            assert_eq!(cert.issued_by, ISSUER);
+            assert_eq!(cert.cert_type, WHMIS);
+            assert_eq!(cert.content, CertificateContent::Inline(BoundedVec::truncate_from(metadata("Jane Doe"))));
+            assert_eq!(cert.issued_at, 1);
+            assert_eq!(cert.expires_at, at_block(50));
+            assert_eq!(cert.status, CertificateStatus::Active);
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![0]);
+            assert_eq!(Certificates::certificate_count(), 1);
+            assert!(Certificates::is_certificate_valid(0));
+
+            System::assert_last_event(
+                Event::CertificateIssued {
+                    id: 0,
+                    owner: HOLDER,
+                    issuer: ISSUER,
+                    issued_by: ISSUER,
+                    cert_type: WHMIS,
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn applies_type_default_validity() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_eq!(Certificates::certificates(id).unwrap().expires_at, at_block(1) + WHMIS_VALIDITY);
+        });
+    }
+
+    #[test]
+    fn zero_expiry_never_expires() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, Some(0));
+            run_to_block(10_000);
+            assert!(Certificates::is_certificate_valid(id));
+            assert_eq!(ExpiryIndex::<Test>::iter().count(), 0);
+        });
+    }
+
+    #[test]
+    fn rejects_origin_not_accepted_by_issuer_origin() {
+        build_and_execute(|| {
+            AllowedIssuers::set(vec![OTHER_ISSUER]);
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_unregistered_issuer() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::issue_cert(
+                    RuntimeOrigin::signed(OTHER_ISSUER),
+                    HOLDER,
+                    WHMIS,
+                    metadata("Jane Doe"),
+                    None,
+                ),
+                Error::<Test>::IssuerNotRegistered
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_suspended_issuer() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::suspend_issuer(RuntimeOrigin::root(), ISSUER));
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::IssuerSuspended
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_unregistered_or_disallowed_type() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::register_certificate_type(
+                RuntimeOrigin::root(),
+                3,
+                b"Tower Crane".to_vec(),
+                0,
+                vec![],
+            ));
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, 3, metadata("Jane Doe"), None),
+                Error::<Test>::CertificateTypeNotAllowed
+            );
+
+            assert_ok!(Certificates::remove_certificate_type(RuntimeOrigin::root(), RIGGING));
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, RIGGING, metadata("Jane Doe"), None),
+                Error::<Test>::CertificateTypeNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn validates_metadata() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, vec![b'a'; 65], None),
+                Error::<Test>::MetadataTooLong
+            );
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, b"not json".to_vec(), None),
+                Error::<Test>::InvalidMetadata
+            );
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, b"[\"name\"]".to_vec(), None),
+                Error::<Test>::InvalidMetadata
+            );
+            assert_noop!(
+                Certificates::issue_cert(
+                    RuntimeOrigin::signed(ISSUER),
+                    HOLDER,
+                    WHMIS,
+                    b"{\"title\":\"Jane Doe\"}".to_vec(),
+                    None,
+                ),
+                Error::<Test>::MissingMetadataField
+            );
+            // Rigging has no required fields, but metadata must still be a JSON object
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                RIGGING,
+                b"{}".to_vec(),
+                None,
+            ));
+        });
+    }
+
+    #[test]
+    fn fails_when_account_list_is_full() {
+        build_and_execute(|| {
+            for _ in 0..4 {
+                issue(HOLDER, None);
+            }
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::TooManyCertificates
+            );
+        });
+    }
+
+    #[test]
+    fn fails_on_id_overflow() {
+        build_and_execute(|| {
+            CertificateCount::<Test>::put(u32::MAX - 1);
+            let id = issue(HOLDER, None);
+            assert_eq!(id, u32::MAX - 1);
+            assert_eq!(Certificates::certificate_count(), u32::MAX);
+
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::CertificateAlreadyExists
+            );
+        });
+    }
+
+    #[test]
+    fn weight_covers_prerequisite_check_of_full_list() {
+        use crate::WeightInfo;
+        use frame_support::dispatch::GetDispatchInfo;
+
+        let call = crate::Call::<Test>::issue_cert {
+            recipient: HOLDER,
+            cert_type: WHMIS,
+            metadata: metadata("Jane Doe"),
+            expires_at: None,
+        };
+        let check = <() as WeightInfo>::check_prerequisites(4);
+
+        assert_eq!(
+            call.get_dispatch_info().weight,
+            <() as WeightInfo>::issue_cert(metadata("Jane Doe").len() as u32).saturating_add(check)
+        );
+    }
+}
+
+mod issue_batch {
+    use super::*;
+
+    #[test]
+    fn works() {
+        build_and_execute(|| {
+            let certificates = BoundedVec::truncate_from(vec![
+                (HOLDER, metadata("Jane Doe"), None),
+                (OTHER_HOLDER, metadata("John Roe"), Some(at_block(20))),
+            ]);
+            assert_ok!(Certificates::issue_batch(RuntimeOrigin::signed(ISSUER), WHMIS, certificates));
+
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![0]);
+            assert_eq!(Certificates::account_certificates(OTHER_HOLDER).to_vec(), vec![1]);
+            System::assert_has_event(
+                Event::CertificateIssued {
+                    id: 1,
+                    owner: OTHER_HOLDER,
+                    issuer: ISSUER,
+                    issued_by: ISSUER,
+                    cert_type: WHMIS,
+                }
+                .into(),
+            );
+            System::assert_last_event(Event::BatchIssued { issuer: ISSUER, cert_type: WHMIS, count: 2 }.into());
+        });
+    }
+
+    #[test]
+    fn is_all_or_nothing() {
+        build_and_execute(|| {
+            let certificates = BoundedVec::truncate_from(vec![
+                (HOLDER, metadata("Jane Doe"), None),
+                (OTHER_HOLDER, b"{}".to_vec(), None),
+            ]);
+            assert_noop!(
+                Certificates::issue_batch(RuntimeOrigin::signed(ISSUER), WHMIS, certificates),
+                Error::<Test>::MissingMetadataField
+            );
+            assert!(Certificates::account_certificates(HOLDER).is_empty());
+            assert_eq!(Certificates::certificate_count(), 0);
+        });
+    }
+
+    #[test]
+    fn rejects_empty_batch() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::issue_batch(RuntimeOrigin::signed(ISSUER), WHMIS, BoundedVec::default()),
+                Error::<Test>::EmptyBatch
+            );
+        });
+    }
+}
+
+mod issue_cert_hashed {
+    use super::*;
+
+    #[test]
+    fn works() {
+        build_and_execute(|| {
+            let document = metadata("Jane Doe");
+            assert_ok!(Certificates::issue_cert_hashed(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                WHMIS,
+                BlakeTwo256::hash(&document),
+                Some(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()),
+                None,
+            ));
+
+            let cert = Certificates::certificates(0).unwrap();
+            assert!(matches!(cert.content, CertificateContent::Hashed { locator: Some(_), .. }));
+            assert_eq!(cert.expires_at, at_block(1) + WHMIS_VALIDITY);
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![0]);
+            System::assert_last_event(
+                Event::CertificateIssued {
+                    id: 0,
+                    owner: HOLDER,
+                    issuer: ISSUER,
+                    issued_by: ISSUER,
+                    cert_type: WHMIS,
+                }
+                .into(),
+            );
+
+            assert!(Certificates::verify_metadata(0, &document));
+            assert!(!Certificates::verify_metadata(0, &metadata("John Roe")));
+            assert!(!Certificates::verify_metadata(1, &document));
+        });
+    }
+
+    #[test]
+    fn renewal_keeps_hashed_content() {
+        build_and_execute(|| {
+            let document = metadata("Jane Doe");
+            assert_ok!(Certificates::issue_cert_hashed(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                WHMIS,
+                BlakeTwo256::hash(&document),
+                None,
+                None,
+            ));
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), 0, None, None));
+
+            assert!(Certificates::verify_metadata(1, &document));
+        });
+    }
+
+    #[test]
+    fn rejects_long_locator() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::issue_cert_hashed(
+                    RuntimeOrigin::signed(ISSUER),
+                    HOLDER,
+                    WHMIS,
+                    H256::zero(),
+                    Some(vec![b'a'; 65]),
+                    None,
+                ),
+                Error::<Test>::LocatorTooLong
+            );
+        });
+    }
+
+    #[test]
+    fn checks_issuer_and_type() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::issue_cert_hashed(RuntimeOrigin::signed(OTHER_ISSUER), HOLDER, WHMIS, H256::zero(), None, None),
+                Error::<Test>::IssuerNotRegistered
+            );
+            assert_noop!(
+                Certificates::issue_cert_hashed(RuntimeOrigin::signed(ISSUER), HOLDER, 3, H256::zero(), None, None),
+                Error::<Test>::CertificateTypeNotAllowed
+            );
+        });
+    }
+}
+
+mod revoke_cert {
+    use super::*;
+
+    #[test]
+    fn works() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, Some(at_block(50)));
+            let note_hash = H256::repeat_byte(7);
+            System::set_block_number(5);
+
+            assert_ok!(Certificates::revoke_cert(
+                RuntimeOrigin::signed(ISSUER),
+                id,
+                RevocationReason::Fraud,
+                Some(note_hash),
+            ));
+
+            let cert = Certificates::certificates(id).unwrap();
+            assert_eq!(cert.status, CertificateStatus::Revoked);
+            assert_eq!(
+                cert.revocation,
+                Some(RevocationInfo { reason: RevocationReason::Fraud, note_hash: Some(note_hash), revoked_at: 5 })
+            );
+            assert!(!Certificates::is_certificate_valid(id));
+            assert_eq!(ExpiryIndex::<Test>::get(at_block(50), id), None);
+            System::assert_last_event(
+                Event::CertificateRevoked {
+                    id,
+                    reason: RevocationReason::Fraud,
+                    note_hash: Some(note_hash),
+                    revoked_at: 5,
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn fails_for_missing_certificate() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::revoke_cert(RuntimeOrigin::signed(ISSUER), 0, RevocationReason::IssuedInError, None),
+                Error::<Test>::CertificateNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn fails_for_another_issuer() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_noop!(
+                Certificates::revoke_cert(RuntimeOrigin::signed(OTHER_ISSUER), id, RevocationReason::Fraud, None),
+                Error::<Test>::NotCertificateOwner
+            );
+        });
+    }
+
+    #[test]
+    fn fails_when_already_revoked() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::revoke_cert(RuntimeOrigin::signed(ISSUER), id, RevocationReason::Fraud, None));
+            assert_noop!(
+                Certificates::revoke_cert(RuntimeOrigin::signed(ISSUER), id, RevocationReason::Fraud, None),
+                Error::<Test>::CertificateAlreadyRevoked
+            );
+        });
+    }
+}
+
+mod suspension {
+    use super::*;
+
+    #[test]
+    fn suspend_and_reinstate_work() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), id));
+            assert_eq!(Certificates::certificate_status(id), Some(CertificateStatus::Suspended));
+            assert!(!Certificates::is_certificate_valid(id));
+            System::assert_last_event(Event::CertificateSuspended { id }.into());
+
+            assert_ok!(Certificates::reinstate_cert(RuntimeOrigin::signed(ISSUER), id));
+            assert_eq!(Certificates::certificate_status(id), Some(CertificateStatus::Active));
+            assert!(Certificates::is_certificate_valid(id));
+            System::assert_last_event(Event::CertificateReinstated { id }.into());
+        });
+    }
+
+    #[test]
+    fn suspend_requires_active_certificate() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_noop!(
+                Certificates::suspend_cert(RuntimeOrigin::signed(OTHER_ISSUER), id),
+                Error::<Test>::NotCertificateOwner
+            );
+            assert_ok!(Certificates::revoke_cert(RuntimeOrigin::signed(ISSUER), id, RevocationReason::Fraud, None));
+            assert_noop!(
+                Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), id),
+                Error::<Test>::CertificateNotActive
+            );
+        });
+    }
+
+    #[test]
+    fn reinstate_requires_suspended_certificate_and_active_issuer() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_noop!(
+                Certificates::reinstate_cert(RuntimeOrigin::signed(ISSUER), id),
+                Error::<Test>::CertificateNotSuspended
+            );
+
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), id));
+            assert_ok!(Certificates::suspend_issuer(RuntimeOrigin::root(), ISSUER));
+            assert_noop!(
+                Certificates::reinstate_cert(RuntimeOrigin::signed(ISSUER), id),
+                Error::<Test>::IssuerSuspended
+            );
+        });
+    }
+}
+
+mod renew_cert {
+    use super::*;
+
+    #[test]
+    fn works() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, Some(at_block(50)));
+            let other = issue(HOLDER, None);
+
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), id, None, Some(at_block(200))));
+
+            let renewed = Certificates::certificates(id).unwrap();
+            assert_eq!(renewed.status, CertificateStatus::Superseded);
+            assert_eq!(renewed.superseded_by, Some(2));
+            let successor = Certificates::certificates(2).unwrap();
+            assert_eq!(successor.supersedes, Some(id));
+            assert_eq!(successor.content, renewed.content);
+            assert_eq!(successor.expires_at, at_block(200));
+
+            // The successor takes the renewed certificate's place
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![2, other]);
+            assert_eq!(ExpiryIndex::<Test>::get(at_block(50), id), None);
+            System::assert_last_event(
+                Event::CertificateRenewed { id: 2, supersedes: id, expires_at: at_block(200) }.into(),
+            );
+        });
+    }
+
+    #[test]
+    fn builds_renewal_chain() {
+        build_and_execute(|| {
+            let first = issue(HOLDER, None);
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), first, None, None));
+            assert_ok!(Certificates::renew_cert(
+                RuntimeOrigin::signed(ISSUER),
+                1,
+                Some(metadata("Jane Q. Doe")),
+                None,
+            ));
+
+            for id in 0..3 {
+                let chain: Vec<u32> = Certificates::get_renewal_chain(id).iter().map(|cert| cert.id).collect();
+                assert_eq!(chain, vec![0, 1, 2]);
+            }
+            assert!(Certificates::verify_metadata(2, &metadata("Jane Q. Doe")));
+        });
+    }
+
+    #[test]
+    fn requires_active_certificate_of_caller() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_noop!(
+                Certificates::renew_cert(RuntimeOrigin::signed(OTHER_ISSUER), id, None, None),
+                Error::<Test>::NotCertificateOwner
+            );
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), id));
+            assert_noop!(
+                Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), id, None, None),
+                Error::<Test>::CertificateNotActive
+            );
+            assert_noop!(
+                Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), 42, None, None),
+                Error::<Test>::CertificateNotFound
+            );
+        });
+    }
+}
+
+mod prune_certificates {
+    use super::*;
+
+    #[test]
+    fn removes_certificates_no_longer_in_force() {
+        build_and_execute(|| {
+            let revoked = issue(HOLDER, None);
+            let active = issue(HOLDER, None);
+            let renewed = issue(HOLDER, None);
+            assert_ok!(Certificates::revoke_cert(
+                RuntimeOrigin::signed(ISSUER),
+                revoked,
+                RevocationReason::FailedReassessment,
+                None,
+            ));
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), renewed, None, None));
+            // Renewal already replaced the superseded certificate in the list
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![revoked, active, 3]);
+
+            assert_ok!(Certificates::prune_certificates(RuntimeOrigin::signed(HOLDER)));
+
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![active, 3]);
+            assert!(Certificates::certificates(revoked).is_some());
+            System::assert_last_event(Event::CertificatesPruned { account: HOLDER, count: 1 }.into());
+
+            assert_noop!(
+                Certificates::prune_certificates(RuntimeOrigin::signed(HOLDER)),
+                Error::<Test>::NothingToPrune
+            );
+        });
+    }
+}
+
+mod expiry {
+    use super::*;
+
+    #[test]
+    fn emits_expired_events() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, Some(at_block(3)));
+            run_to_block(2);
+            assert!(Certificates::is_certificate_valid(id));
+
+            run_to_block(3);
+            assert!(!Certificates::is_certificate_valid(id));
+            System::assert_has_event(Event::CertificateExpired { id, owner: HOLDER }.into());
+            assert_eq!(ExpiryIndex::<Test>::iter().count(), 0);
+        });
+    }
+
+    #[test]
+    fn carries_over_overflow() {
+        build_and_execute(|| {
+            // MaxExpiriesPerBlock is 3: one bucket visit plus two expiries per block
+            for holder in 20..25 {
+                issue(holder, Some(at_block(3)));
+            }
+            run_to_block(3);
+            assert_eq!(ExpiryIndex::<Test>::iter_prefix(at_block(3)).count(), 3);
+
+            run_to_block(4);
+            assert_eq!(ExpiryIndex::<Test>::iter_prefix(at_block(3)).count(), 1);
+
+            run_to_block(5);
+            assert_eq!(ExpiryIndex::<Test>::iter().count(), 0);
+            let expired = System::events()
+                .into_iter()
+                .filter(|record| matches!(record.event, RuntimeEvent::Certificates(Event::CertificateExpired { .. })))
+                .count();
+            assert_eq!(expired, 5);
+            assert_eq!(ExpiryCursor::<Test>::get(), Some(at_block(5)));
+        });
+    }
+
+    #[test]
+    fn revoked_certificates_do_not_expire() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, Some(at_block(3)));
+            assert_ok!(Certificates::revoke_cert(RuntimeOrigin::signed(ISSUER), id, RevocationReason::Fraud, None));
+            run_to_block(3);
+            assert!(!System::events()
+                .into_iter()
+                .any(|record| matches!(record.event, RuntimeEvent::Certificates(Event::CertificateExpired { .. }))));
+        });
+    }
+
+    #[test]
+    fn validity_follows_wall_clock_not_blocks() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, Some(at_block(1) + 1_000));
+            assert!(Certificates::is_certificate_valid(id));
+
+            // Time moves on without a new block, e.g. after a block time change
+            Timestamp::set_timestamp(at_block(1) + 1_000);
+            assert!(!Certificates::is_certificate_valid(id));
+        });
+    }
+
+    #[test]
+    fn reports_expiry_once_its_bucket_has_ended() {
+        build_and_execute(|| {
+            // Expires a third of the way into the bucket ending at block 3
+            let id = issue(HOLDER, Some(at_block(2) + 2_000));
+            run_to_block(2);
+            Timestamp::set_timestamp(at_block(2) + 2_000);
+            assert!(!Certificates::is_certificate_valid(id));
+            assert_eq!(ExpiryIndex::<Test>::iter_keys().collect::<Vec<_>>(), vec![(at_block(3), id)]);
+
+            run_to_block(3);
+            System::assert_has_event(Event::CertificateExpired { id, owner: HOLDER }.into());
+        });
+    }
+
+    #[test]
+    fn nothing_is_processed_before_time_is_known() {
+        build_and_execute(|| {
+            Timestamp::set_timestamp(0);
+            Certificates::on_initialize(1);
+            assert_eq!(ExpiryCursor::<Test>::get(), None);
+        });
+    }
+}
+
+mod issuer_registry {
+    use super::*;
+
+    #[test]
+    fn register_works() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::register_issuer(
+                RuntimeOrigin::root(),
+                OTHER_ISSUER,
+                b"Beta Rigging".to_vec(),
+                b"ACC-002".to_vec(),
+                b"https://beta.example".to_vec(),
+                vec![RIGGING],
+            ));
+
+            let info = Certificates::issuers(OTHER_ISSUER).unwrap();
+            assert_eq!(info.legal_name.to_vec(), b"Beta Rigging".to_vec());
+            assert_eq!(info.certificate_types.to_vec(), vec![RIGGING]);
+            assert_eq!(info.status, IssuerStatus::Active);
+            System::assert_last_event(Event::IssuerRegistered { issuer: OTHER_ISSUER }.into());
+        });
+    }
+
+    #[test]
+    fn register_requires_registrar_origin() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::register_issuer(
+                    RuntimeOrigin::signed(ISSUER),
+                    OTHER_ISSUER,
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![],
+                ),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn register_validates_input() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::register_issuer(RuntimeOrigin::root(), ISSUER, vec![], vec![], vec![], vec![]),
+                Error::<Test>::IssuerAlreadyRegistered
+            );
+            assert_noop!(
+                Certificates::register_issuer(RuntimeOrigin::root(), OTHER_ISSUER, vec![b'a'; 33], vec![], vec![], vec![]),
+                Error::<Test>::IssuerFieldTooLong
+            );
+            assert_noop!(
+                Certificates::register_issuer(RuntimeOrigin::root(), OTHER_ISSUER, vec![], vec![], vec![], vec![42]),
+                Error::<Test>::CertificateTypeNotFound
+            );
+            assert_noop!(
+                Certificates::register_issuer(
+                    RuntimeOrigin::root(),
+                    OTHER_ISSUER,
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![WHMIS, RIGGING, WHMIS, RIGGING],
+                ),
+                Error::<Test>::TooManyCertificateTypes
+            );
+        });
+    }
+
+    #[test]
+    fn suspend_reinstate_and_remove_work() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::suspend_issuer(RuntimeOrigin::root(), ISSUER));
+            System::assert_last_event(Event::IssuerSuspended { issuer: ISSUER }.into());
+            assert_noop!(
+                Certificates::suspend_issuer(RuntimeOrigin::root(), ISSUER),
+                Error::<Test>::IssuerSuspended
+            );
+
+            assert_ok!(Certificates::reinstate_issuer(RuntimeOrigin::root(), ISSUER));
+            System::assert_last_event(Event::IssuerReinstated { issuer: ISSUER }.into());
+            assert_noop!(
+                Certificates::reinstate_issuer(RuntimeOrigin::root(), ISSUER),
+                Error::<Test>::IssuerNotSuspended
+            );
+
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::remove_issuer(RuntimeOrigin::root(), ISSUER));
+            System::assert_last_event(Event::IssuerRemoved { issuer: ISSUER }.into());
+            assert!(Certificates::issuers(ISSUER).is_none());
+            // Existing certificates are kept
+            assert!(Certificates::is_certificate_valid(id));
+
+            assert_noop!(
+                Certificates::remove_issuer(RuntimeOrigin::root(), ISSUER),
+                Error::<Test>::IssuerNotRegistered
+            );
+            assert_noop!(
+                Certificates::suspend_issuer(RuntimeOrigin::root(), ISSUER),
+                Error::<Test>::IssuerNotRegistered
+            );
+        });
+    }
+
+    #[test]
+    fn remove_issuer_clears_its_keys_delegates_and_accreditations() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::register_accreditor(RuntimeOrigin::root(), ACCREDITOR, b"Council".to_vec()));
+            assert_ok!(Certificates::accredit_issuer(RuntimeOrigin::signed(ACCREDITOR), ISSUER, WHMIS, at_block(50)));
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+
+            assert_ok!(Certificates::remove_issuer(RuntimeOrigin::root(), ISSUER));
+
+            assert_eq!(Certificates::accreditations(ISSUER, WHMIS), None);
+            assert_eq!(Certificates::delegates(INSTRUCTOR), None);
+            assert!(Certificates::issuer_delegates(ISSUER).is_empty());
+            assert!(Certificates::issuer_keys(ISSUER).is_empty());
+            assert_eq!(Certificates::signing_keys(NEW_KEY), None);
+            // The keys stay retired while the identity account is free again
+            assert_eq!(Certificates::retired_keys(NEW_KEY), Some(ISSUER));
+            assert_eq!(Certificates::retired_keys(ISSUER), None);
+        });
+    }
+
+    #[test]
+    fn removed_issuer_can_be_registered_again() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+            assert_ok!(Certificates::remove_issuer(RuntimeOrigin::root(), ISSUER));
+
+            assert_ok!(Certificates::register_issuer(
+                RuntimeOrigin::root(),
+                ISSUER,
+                b"Acme Safety Training".to_vec(),
+                b"ACC-002".to_vec(),
+                vec![],
+                vec![WHMIS],
+            ));
+
+            let id = issue(HOLDER, None);
+            assert_eq!(Certificates::certificates(id).unwrap().issued_by, ISSUER);
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(NEW_KEY), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::IssuerKeyRetired
+            );
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(INSTRUCTOR), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::IssuerNotRegistered
+            );
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+        });
+    }
+}
+
+mod certificate_type_registry {
+    use super::*;
+
+    #[test]
+    fn register_and_remove_work() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::register_certificate_type(
+                RuntimeOrigin::root(),
+                3,
+                b"Tower Crane".to_vec(),
+                500,
+                vec![b"name".to_vec(), b"hours".to_vec()],
+            ));
+            let info = Certificates::certificate_types(3).unwrap();
+            assert_eq!(info.name.to_vec(), b"Tower Crane".to_vec());
+            assert_eq!(info.default_validity, 500);
+            assert_eq!(info.required_fields.len(), 2);
+            System::assert_last_event(Event::CertificateTypeRegistered { type_id: 3 }.into());
+
+            assert_ok!(Certificates::remove_certificate_type(RuntimeOrigin::root(), 3));
+            assert!(Certificates::certificate_types(3).is_none());
+            System::assert_last_event(Event::CertificateTypeRemoved { type_id: 3 }.into());
+            assert_noop!(
+                Certificates::remove_certificate_type(RuntimeOrigin::root(), 3),
+                Error::<Test>::CertificateTypeNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn register_validates_input() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::register_certificate_type(RuntimeOrigin::root(), WHMIS, b"WHMIS".to_vec(), 0, vec![]),
+                Error::<Test>::CertificateTypeAlreadyExists
+            );
+            assert_noop!(
+                Certificates::register_certificate_type(RuntimeOrigin::root(), 3, vec![0xff, 0xfe], 0, vec![]),
+                Error::<Test>::InvalidTypeName
+            );
+            assert_noop!(
+                Certificates::register_certificate_type(RuntimeOrigin::root(), 3, vec![b'a'; 17], 0, vec![]),
+                Error::<Test>::InvalidTypeName
+            );
+            assert_noop!(
+                Certificates::register_certificate_type(
+                    RuntimeOrigin::root(),
+                    3,
+                    b"Tower Crane".to_vec(),
+                    0,
+                    vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()],
+                ),
+                Error::<Test>::TooManyRequiredFields
+            );
+            assert_noop!(
+                Certificates::register_certificate_type(RuntimeOrigin::signed(ISSUER), 3, vec![], 0, vec![]),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+}
+
+mod prerequisites {
+    use super::*;
+
+    fn require_rigging(enforce_at_issuance: bool) {
+        assert_ok!(Certificates::set_prerequisites(RuntimeOrigin::root(), WHMIS, vec![RIGGING], enforce_at_issuance));
+    }
+
+    fn issue_rigging(holder: u64, expires_at: Option<u64>) -> u32 {
+        let id = Certificates::certificate_count();
+        assert_ok!(Certificates::issue_cert(
+            RuntimeOrigin::signed(ISSUER),
+            holder,
+            RIGGING,
+            metadata("Jane Doe"),
+            expires_at,
+        ));
+        id
+    }
+
+    #[test]
+    fn set_and_remove_work() {
+        build_and_execute(|| {
+            require_rigging(true);
+            let rule = Certificates::prerequisites(WHMIS).unwrap();
+            assert_eq!(rule.certificate_types.to_vec(), vec![RIGGING]);
+            assert!(rule.enforce_at_issuance);
+            System::assert_last_event(
+                Event::PrerequisitesSet { type_id: WHMIS, prerequisites: vec![RIGGING], enforce_at_issuance: true }
+                    .into(),
+            );
+
+            assert_ok!(Certificates::set_prerequisites(RuntimeOrigin::root(), WHMIS, vec![], false));
+            assert!(Certificates::prerequisites(WHMIS).is_none());
+        });
+    }
+
+    #[test]
+    fn set_validates_input() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::set_prerequisites(RuntimeOrigin::signed(ISSUER), WHMIS, vec![RIGGING], true),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Certificates::set_prerequisites(RuntimeOrigin::root(), 42, vec![RIGGING], true),
+                Error::<Test>::CertificateTypeNotFound
+            );
+            assert_noop!(
+                Certificates::set_prerequisites(RuntimeOrigin::root(), WHMIS, vec![42], true),
+                Error::<Test>::CertificateTypeNotFound
+            );
+            assert_noop!(
+                Certificates::set_prerequisites(RuntimeOrigin::root(), WHMIS, vec![RIGGING, WHMIS], true),
+                Error::<Test>::InvalidPrerequisite
+            );
+
+            for type_id in 3..5 {
+                assert_ok!(Certificates::register_certificate_type(
+                    RuntimeOrigin::root(),
+                    type_id,
+                    b"Tower Crane".to_vec(),
+                    0,
+                    vec![],
+                ));
+            }
+            assert_noop!(
+                Certificates::set_prerequisites(RuntimeOrigin::root(), WHMIS, vec![RIGGING, 3, 4], true),
+                Error::<Test>::TooManyPrerequisites
+            );
+        });
+    }
+
+    #[test]
+    fn enforced_at_issuance() {
+        build_and_execute(|| {
+            require_rigging(true);
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::PrerequisiteNotMet
+            );
+
+            let rigging = issue_rigging(HOLDER, None);
+            let id = issue(HOLDER, None);
+            assert!(Certificates::is_certificate_effective(id));
+
+            // Renewals are issuances too
+            assert_ok!(Certificates::revoke_cert(
+                RuntimeOrigin::signed(ISSUER),
+                rigging,
+                RevocationReason::FailedReassessment,
+                None,
+            ));
+            assert_noop!(
+                Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), id, None, None),
+                Error::<Test>::PrerequisiteNotMet
+            );
+        });
+    }
+
+    #[test]
+    fn enforced_for_every_batch_entry() {
+        build_and_execute(|| {
+            require_rigging(true);
+            issue_rigging(HOLDER, None);
+
+            let certificates = BoundedVec::truncate_from(vec![
+                (HOLDER, metadata("Jane Doe"), None),
+                (OTHER_HOLDER, metadata("John Roe"), None),
+            ]);
+            assert_noop!(
+                Certificates::issue_batch(RuntimeOrigin::signed(ISSUER), WHMIS, certificates),
+                Error::<Test>::PrerequisiteNotMet
+            );
+        });
+    }
+
+    #[test]
+    fn lapsed_prerequisite_makes_certificate_ineffective() {
+        build_and_execute(|| {
+            require_rigging(false);
+            let id = issue(HOLDER, None);
+            assert!(Certificates::is_certificate_valid(id));
+            assert!(!Certificates::is_certificate_effective(id));
+
+            issue_rigging(HOLDER, Some(at_block(5)));
+            assert!(Certificates::is_certificate_effective(id));
+
+            run_to_block(5);
+            assert!(Certificates::is_certificate_valid(id));
+            assert!(!Certificates::is_certificate_effective(id));
+        });
+    }
+
+    #[test]
+    fn revoked_prerequisite_makes_certificate_ineffective() {
+        build_and_execute(|| {
+            require_rigging(true);
+            let rigging = issue_rigging(HOLDER, None);
+            let id = issue(HOLDER, None);
+
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), rigging));
+            assert!(!Certificates::is_certificate_effective(id));
+            assert_ok!(Certificates::reinstate_cert(RuntimeOrigin::signed(ISSUER), rigging));
+            assert!(Certificates::is_certificate_effective(id));
+
+            assert_ok!(Certificates::revoke_cert(RuntimeOrigin::signed(ISSUER), rigging, RevocationReason::Fraud, None));
+            assert!(!Certificates::is_certificate_effective(id));
+        });
+    }
+
+    #[test]
+    fn removed_with_certificate_type() {
+        build_and_execute(|| {
+            require_rigging(true);
+            assert_ok!(Certificates::remove_certificate_type(RuntimeOrigin::root(), WHMIS));
+            assert!(Certificates::prerequisites(WHMIS).is_none());
+        });
+    }
+}
+
+mod site_profiles {
+    use super::*;
+    use crate::ComplianceReport;
+
+    fn create_site() -> u32 {
+        let site_id = Certificates::site_count();
+        assert_ok!(Certificates::create_site_profile(
+            RuntimeOrigin::signed(SITE_OWNER),
+            b"Harbour Tower".to_vec(),
+            vec![(WHMIS, 10 * MILLISECS_PER_BLOCK), (RIGGING, 0)],
+        ));
+        site_id
+    }
+
+    #[test]
+    fn create_works() {
+        build_and_execute(|| {
+            let site_id = create_site();
+
+            let profile = Certificates::site_profiles(site_id).unwrap();
+            assert_eq!(profile.owner, SITE_OWNER);
+            assert_eq!(profile.name.to_vec(), b"Harbour Tower".to_vec());
+            assert_eq!(profile.requirements.len(), 2);
+            assert_eq!(profile.requirements[0].cert_type, WHMIS);
+            assert_eq!(profile.requirements[0].min_remaining_validity, 10 * MILLISECS_PER_BLOCK);
+            assert_eq!(profile.deposit, SITE_PROFILE_DEPOSIT);
+            assert_eq!(Balances::reserved_balance(SITE_OWNER), SITE_PROFILE_DEPOSIT);
+            assert_eq!(Certificates::site_count(), 1);
+            System::assert_last_event(Event::SiteProfileCreated { site_id, owner: SITE_OWNER }.into());
+        });
+    }
+
+    #[test]
+    fn create_validates_input() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::create_site_profile(RuntimeOrigin::signed(SITE_OWNER), vec![b'a'; 17], vec![]),
+                Error::<Test>::SiteNameTooLong
+            );
+            assert_noop!(
+                Certificates::create_site_profile(RuntimeOrigin::signed(SITE_OWNER), vec![], vec![(42, 0)]),
+                Error::<Test>::CertificateTypeNotFound
+            );
+            assert_noop!(
+                Certificates::create_site_profile(
+                    RuntimeOrigin::signed(SITE_OWNER),
+                    vec![],
+                    vec![(WHMIS, 0), (RIGGING, 0), (WHMIS, 10), (RIGGING, 10)],
+                ),
+                Error::<Test>::TooManySiteRequirements
+            );
+            assert_noop!(
+                Certificates::create_site_profile(RuntimeOrigin::root(), vec![], vec![]),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn create_requires_deposit() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::create_site_profile(RuntimeOrigin::signed(HOLDER), b"Harbour Tower".to_vec(), vec![]),
+                pallet_balances::Error::<Test>::InsufficientBalance
+            );
+        });
+    }
+
+    #[test]
+    fn create_fails_on_id_overflow() {
+        build_and_execute(|| {
+            crate::SiteCount::<Test>::put(u32::MAX);
+            assert_noop!(
+                Certificates::create_site_profile(RuntimeOrigin::signed(SITE_OWNER), vec![], vec![]),
+                Error::<Test>::TooManySiteProfiles
+            );
+        });
+    }
+
+    #[test]
+    fn update_and_remove_are_restricted_to_owner() {
+        build_and_execute(|| {
+            let site_id = create_site();
+
+            assert_noop!(
+                Certificates::update_site_profile(RuntimeOrigin::signed(HOLDER), site_id, vec![], vec![]),
+                Error::<Test>::NotSiteOwner
+            );
+            assert_noop!(
+                Certificates::remove_site_profile(RuntimeOrigin::signed(HOLDER), site_id),
+                Error::<Test>::NotSiteOwner
+            );
+
+            assert_ok!(Certificates::update_site_profile(
+                RuntimeOrigin::signed(SITE_OWNER),
+                site_id,
+                b"Harbour Tower B".to_vec(),
+                vec![(RIGGING, 0)],
+            ));
+            assert_eq!(Certificates::site_profiles(site_id).unwrap().requirements.len(), 1);
+            System::assert_last_event(Event::SiteProfileUpdated { site_id }.into());
+
+            assert_ok!(Certificates::remove_site_profile(RuntimeOrigin::signed(SITE_OWNER), site_id));
+            assert!(Certificates::site_profiles(site_id).is_none());
+            assert_eq!(Balances::reserved_balance(SITE_OWNER), 0);
+            System::assert_last_event(Event::SiteProfileRemoved { site_id }.into());
+
+            assert_noop!(
+                Certificates::update_site_profile(RuntimeOrigin::signed(SITE_OWNER), site_id, vec![], vec![]),
+                Error::<Test>::SiteProfileNotFound
+            );
+            assert_noop!(
+                Certificates::remove_site_profile(RuntimeOrigin::signed(SITE_OWNER), site_id),
+                Error::<Test>::SiteProfileNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn check_compliance_reports_missing_and_expiring() {
+        build_and_execute(|| {
+            assert_eq!(Certificates::check_compliance(&HOLDER, 0), None);
+            let site_id = create_site();
+
+            assert_eq!(
+                Certificates::check_compliance(&HOLDER, site_id),
+                Some(ComplianceReport { compliant: false, missing: vec![WHMIS, RIGGING], expiring: vec![] })
+            );
+
+            // WHMIS expires at block 101 by default
+            let whmis = issue(HOLDER, None);
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                RIGGING,
+                metadata("Jane Doe"),
+                None,
+            ));
+            assert_eq!(
+                Certificates::check_compliance(&HOLDER, site_id),
+                Some(ComplianceReport { compliant: true, missing: vec![], expiring: vec![] })
+            );
+
+            run_to_block(92);
+            assert_eq!(
+                Certificates::check_compliance(&HOLDER, site_id),
+                Some(ComplianceReport { compliant: false, missing: vec![], expiring: vec![(WHMIS, whmis)] })
+            );
+
+            // A later certificate of the same type counts instead
+            issue(HOLDER, Some(at_block(300)));
+            assert!(Certificates::check_compliance(&HOLDER, site_id).unwrap().compliant);
+        });
+    }
+
+    #[test]
+    fn check_compliance_ignores_certificates_not_in_force() {
+        build_and_execute(|| {
+            let site_id = create_site();
+            let whmis = issue(HOLDER, None);
+            let rigging = Certificates::certificate_count();
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                RIGGING,
+                metadata("Jane Doe"),
+                Some(at_block(20)),
+            ));
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), whmis));
+
+            assert_eq!(Certificates::check_compliance(&HOLDER, site_id).unwrap().missing, vec![WHMIS]);
+
+            assert_ok!(Certificates::reinstate_cert(RuntimeOrigin::signed(ISSUER), whmis));
+            assert_ok!(Certificates::set_prerequisites(RuntimeOrigin::root(), WHMIS, vec![RIGGING], false));
+            run_to_block(20);
+
+            // The lapsed Rigging certificate also takes the WHMIS certificate out of force
+            assert!(!Certificates::is_certificate_valid(rigging));
+            assert_eq!(Certificates::check_compliance(&HOLDER, site_id).unwrap().missing, vec![WHMIS, RIGGING]);
+        });
+    }
+}
+
+mod delegates {
+    use super::*;
+
+    fn add_instructors() {
+        assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+        assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), OTHER_INSTRUCTOR));
+    }
+
+    #[test]
+    fn add_delegate_works() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+
+            assert_eq!(Certificates::delegates(INSTRUCTOR), Some(ISSUER));
+            System::assert_last_event(Event::DelegateAdded { issuer: ISSUER, delegate: INSTRUCTOR }.into());
+        });
+    }
+
+    #[test]
+    fn add_delegate_checks_accounts() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::add_delegate(RuntimeOrigin::signed(OTHER_ISSUER), INSTRUCTOR),
+                Error::<Test>::IssuerNotRegistered
+            );
+
+            assert_ok!(Certificates::register_issuer(
+                RuntimeOrigin::root(),
+                OTHER_ISSUER,
+                vec![],
+                vec![],
+                vec![],
+                vec![WHMIS],
+            ));
+            assert_noop!(
+                Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), OTHER_ISSUER),
+                Error::<Test>::DelegateIsIssuer
+            );
+
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            assert_noop!(
+                Certificates::add_delegate(RuntimeOrigin::signed(OTHER_ISSUER), INSTRUCTOR),
+                Error::<Test>::AlreadyDelegate
+            );
+        });
+    }
+
+    #[test]
+    fn delegate_cannot_be_registered_as_issuer() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            assert_noop!(
+                Certificates::register_issuer(RuntimeOrigin::root(), INSTRUCTOR, vec![], vec![], vec![], vec![]),
+                Error::<Test>::AlreadyDelegate
+            );
+        });
+    }
+
+    #[test]
+    fn delegate_issues_on_behalf_of_issuer() {
+        build_and_execute(|| {
+            add_instructors();
+
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(INSTRUCTOR),
+                HOLDER,
+                WHMIS,
+                metadata("Jane Doe"),
+                None,
+            ));
+
+            let cert = Certificates::certificates(0).unwrap();
+            assert_eq!(cert.issuer, ISSUER);
+            assert_eq!(cert.issued_by, INSTRUCTOR);
+            let (page, _) = Certificates::get_issuer_certificates(&ISSUER, None, 10);
+            assert_eq!(page.len(), 1);
+            System::assert_last_event(
+                Event::CertificateIssued {
+                    id: 0,
+                    owner: HOLDER,
+                    issuer: ISSUER,
+                    issued_by: INSTRUCTOR,
+                    cert_type: WHMIS,
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn delegate_of_suspended_issuer_cannot_issue() {
+        build_and_execute(|| {
+            add_instructors();
+            assert_ok!(Certificates::suspend_issuer(RuntimeOrigin::root(), ISSUER));
+
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(INSTRUCTOR), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::IssuerSuspended
+            );
+        });
+    }
+
+    #[test]
+    fn any_delegate_manages_certificates_after_staff_turnover() {
+        build_and_execute(|| {
+            add_instructors();
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(INSTRUCTOR),
+                HOLDER,
+                WHMIS,
+                metadata("Jane Doe"),
+                None,
+            ));
+            assert_ok!(Certificates::remove_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+
+            // The departed instructor no longer acts for the issuer
+            assert_noop!(
+                Certificates::suspend_cert(RuntimeOrigin::signed(INSTRUCTOR), 0),
+                Error::<Test>::NotCertificateOwner
+            );
+
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(OTHER_INSTRUCTOR), 0));
+            assert_ok!(Certificates::reinstate_cert(RuntimeOrigin::signed(ISSUER), 0));
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(OTHER_INSTRUCTOR), 0, None, None));
+            assert_eq!(Certificates::certificates(1).unwrap().issued_by, OTHER_INSTRUCTOR);
+            assert_ok!(Certificates::revoke_cert(
+                RuntimeOrigin::signed(OTHER_INSTRUCTOR),
+                1,
+                RevocationReason::FailedReassessment,
+                None,
+            ));
+            assert_eq!(Certificates::certificate_status(1), Some(CertificateStatus::Revoked));
+        });
+    }
+
+    #[test]
+    fn delegate_of_another_issuer_cannot_revoke() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::register_issuer(
+                RuntimeOrigin::root(),
+                OTHER_ISSUER,
+                vec![],
+                vec![],
+                vec![],
+                vec![WHMIS],
+            ));
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(OTHER_ISSUER), INSTRUCTOR));
+
+            assert_noop!(
+                Certificates::revoke_cert(RuntimeOrigin::signed(INSTRUCTOR), id, RevocationReason::Fraud, None),
+                Error::<Test>::NotCertificateOwner
+            );
+        });
+    }
+
+    #[test]
+    fn remove_delegate_works_for_issuer_and_delegate() {
+        build_and_execute(|| {
+            add_instructors();
+
+            assert_ok!(Certificates::remove_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            System::assert_last_event(Event::DelegateRemoved { issuer: ISSUER, delegate: INSTRUCTOR }.into());
+            assert_eq!(Certificates::delegates(INSTRUCTOR), None);
+
+            // A delegate may step down itself
+            assert_ok!(Certificates::remove_delegate(RuntimeOrigin::signed(OTHER_INSTRUCTOR), OTHER_INSTRUCTOR));
+            assert_eq!(Certificates::delegates(OTHER_INSTRUCTOR), None);
+        });
+    }
+
+    #[test]
+    fn delegates_per_issuer_are_bounded() {
+        build_and_execute(|| {
+            add_instructors();
+
+            assert_noop!(
+                Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), HOLDER),
+                Error::<Test>::TooManyDelegates
+            );
+
+            assert_ok!(Certificates::remove_delegate(RuntimeOrigin::signed(INSTRUCTOR), INSTRUCTOR));
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), HOLDER));
+            assert_eq!(Certificates::issuer_delegates(ISSUER).to_vec(), vec![OTHER_INSTRUCTOR, HOLDER]);
+        });
+    }
+
+    #[test]
+    fn remove_delegate_checks_caller() {
+        build_and_execute(|| {
+            add_instructors();
+
+            assert_noop!(
+                Certificates::remove_delegate(RuntimeOrigin::signed(OTHER_INSTRUCTOR), INSTRUCTOR),
+                Error::<Test>::NotDelegate
+            );
+            assert_noop!(
+                Certificates::remove_delegate(RuntimeOrigin::signed(OTHER_ISSUER), INSTRUCTOR),
+                Error::<Test>::NotDelegate
+            );
+            assert_noop!(
+                Certificates::remove_delegate(RuntimeOrigin::signed(ISSUER), HOLDER),
+                Error::<Test>::NotDelegate
+            );
+        });
+    }
+}
+
+mod key_rotation {
+    use super::*;
+
+    #[test]
+    fn rotate_issuer_key_works() {
+        build_and_execute(|| {
+            System::set_block_number(5);
+
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+
+            assert_eq!(
+                Certificates::issuer_keys(ISSUER).to_vec(),
+                vec![
+                    IssuerKey { key: ISSUER, valid_from: 1, valid_until: Some(5) },
+                    IssuerKey { key: NEW_KEY, valid_from: 5, valid_until: None },
+                ]
+            );
+            assert_eq!(Certificates::signing_keys(NEW_KEY), Some(ISSUER));
+            System::assert_last_event(
+                Event::IssuerKeyRotated { issuer: ISSUER, old_key: ISSUER, new_key: NEW_KEY }.into(),
+            );
+
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(NEW_KEY),
+                HOLDER,
+                WHMIS,
+                metadata("Jane Doe"),
+                None,
+            ));
+            let cert = Certificates::certificates(0).unwrap();
+            assert_eq!(cert.issuer, ISSUER);
+            assert_eq!(cert.issued_by, NEW_KEY);
+        });
+    }
+
+    #[test]
+    fn retired_key_no_longer_acts_for_issuer() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::IssuerKeyRetired
+            );
+            assert_noop!(
+                Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR),
+                Error::<Test>::IssuerKeyRetired
+            );
+        });
+    }
+
+    #[test]
+    fn new_key_revokes_certificates_signed_with_old_key() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+
+            assert_ok!(Certificates::revoke_cert(RuntimeOrigin::signed(NEW_KEY), id, RevocationReason::Fraud, None));
+            assert_eq!(Certificates::certificates(id).unwrap().issued_by, ISSUER);
+        });
+    }
+
+    #[test]
+    fn registrar_replaces_lost_key() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::root(), ISSUER, NEW_KEY));
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::root(), ISSUER, OTHER_NEW_KEY));
+
+            assert_eq!(Certificates::signing_keys(NEW_KEY), None);
+            assert_noop!(
+                Certificates::suspend_cert(RuntimeOrigin::signed(NEW_KEY), id),
+                Error::<Test>::IssuerKeyRetired
+            );
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(OTHER_NEW_KEY), id));
+        });
+    }
+
+    #[test]
+    fn delegates_keep_acting_after_rotation() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(INSTRUCTOR),
+                HOLDER,
+                WHMIS,
+                metadata("Jane Doe"),
+                None,
+            ));
+            assert_eq!(Certificates::certificates(0).unwrap().issuer, ISSUER);
+
+            assert_ok!(Certificates::remove_delegate(RuntimeOrigin::signed(NEW_KEY), INSTRUCTOR));
+        });
+    }
+
+    #[test]
+    fn only_current_key_or_registrar_may_rotate() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+
+            assert_noop!(
+                Certificates::rotate_issuer_key(RuntimeOrigin::signed(OTHER_ISSUER), ISSUER, NEW_KEY),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Certificates::rotate_issuer_key(RuntimeOrigin::signed(INSTRUCTOR), ISSUER, NEW_KEY),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Certificates::rotate_issuer_key(RuntimeOrigin::root(), OTHER_ISSUER, NEW_KEY),
+                Error::<Test>::IssuerNotRegistered
+            );
+        });
+    }
+
+    #[test]
+    fn new_key_must_be_unused() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            assert_ok!(Certificates::register_issuer(
+                RuntimeOrigin::root(),
+                OTHER_ISSUER,
+                vec![],
+                vec![],
+                vec![],
+                vec![WHMIS],
+            ));
+
+            for key in [ISSUER, INSTRUCTOR, OTHER_ISSUER] {
+                assert_noop!(
+                    Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, key),
+                    Error::<Test>::KeyInUse
+                );
+            }
+
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(OTHER_ISSUER), OTHER_ISSUER, NEW_KEY));
+            assert_noop!(
+                Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY),
+                Error::<Test>::KeyInUse
+            );
+            assert_noop!(
+                Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), NEW_KEY),
+                Error::<Test>::DelegateIsIssuer
+            );
+            assert_noop!(
+                Certificates::register_issuer(RuntimeOrigin::root(), NEW_KEY, vec![], vec![], vec![], vec![]),
+                Error::<Test>::KeyInUse
+            );
+        });
+    }
+
+    #[test]
+    fn retired_keys_cannot_be_reused() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(NEW_KEY), ISSUER, OTHER_NEW_KEY));
+            assert_eq!(Certificates::retired_keys(ISSUER), Some(ISSUER));
+            assert_eq!(Certificates::retired_keys(NEW_KEY), Some(ISSUER));
+
+            for key in [ISSUER, NEW_KEY] {
+                assert_noop!(
+                    Certificates::rotate_issuer_key(RuntimeOrigin::signed(OTHER_NEW_KEY), ISSUER, key),
+                    Error::<Test>::KeyInUse
+                );
+                assert_noop!(
+                    Certificates::rotate_issuer_key(RuntimeOrigin::root(), ISSUER, key),
+                    Error::<Test>::KeyInUse
+                );
+            }
+            assert_noop!(
+                Certificates::add_delegate(RuntimeOrigin::signed(OTHER_NEW_KEY), NEW_KEY),
+                Error::<Test>::DelegateIsIssuer
+            );
+            assert_noop!(
+                Certificates::register_issuer(RuntimeOrigin::root(), NEW_KEY, vec![], vec![], vec![], vec![]),
+                Error::<Test>::KeyInUse
+            );
+        });
+    }
+
+    #[test]
+    fn every_retired_key_stops_acting() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(NEW_KEY), ISSUER, OTHER_NEW_KEY));
+
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(NEW_KEY), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::IssuerKeyRetired
+            );
+            assert_noop!(
+                Certificates::revoke_cert(RuntimeOrigin::signed(NEW_KEY), id, RevocationReason::Fraud, None),
+                Error::<Test>::IssuerKeyRetired
+            );
+        });
+    }
+
+    #[test]
+    fn issuer_key_at_follows_history() {
+        build_and_execute(|| {
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 1), Some(ISSUER));
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 0), None);
+            assert_eq!(Certificates::issuer_key_at(&OTHER_ISSUER, 1), None);
+
+            System::set_block_number(10);
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(ISSUER), ISSUER, NEW_KEY));
+            System::set_block_number(20);
+            assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::signed(NEW_KEY), ISSUER, OTHER_NEW_KEY));
+
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 9), Some(ISSUER));
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 10), Some(NEW_KEY));
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 19), Some(NEW_KEY));
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 20), Some(OTHER_NEW_KEY));
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 1_000), Some(OTHER_NEW_KEY));
+        });
+    }
+
+    #[test]
+    fn full_history_drops_oldest_keys() {
+        build_and_execute(|| {
+            for (block, key) in [(5, NEW_KEY), (6, OTHER_NEW_KEY), (7, INSTRUCTOR)] {
+                System::set_block_number(block);
+                assert_ok!(Certificates::rotate_issuer_key(RuntimeOrigin::root(), ISSUER, key));
+            }
+
+            let keys = Certificates::issuer_keys(ISSUER).into_iter().map(|entry| entry.key).collect::<Vec<_>>();
+            assert_eq!(keys, vec![NEW_KEY, OTHER_NEW_KEY, INSTRUCTOR]);
+            assert_eq!(Certificates::issuer_key_at(&ISSUER, 1), None);
+            // The identity account stays retired after its entry is dropped
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                Error::<Test>::IssuerKeyRetired
+            );
+        });
+    }
+}
+
+mod deposits {
+    use super::*;
+
+    /// Deposit for a certificate carrying `metadata("Jane Doe")`
+    const DEPOSIT: u64 = DEPOSIT_BASE + 19 * DEPOSIT_PER_BYTE;
+
+    #[test]
+    fn issuance_reserves_deposit_from_issuer() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+
+            assert_eq!(Certificates::certificates(id).unwrap().deposit, DEPOSIT);
+            assert_eq!(Balances::reserved_balance(ISSUER), DEPOSIT);
+            assert_eq!(Balances::free_balance(ISSUER), INITIAL_BALANCE - DEPOSIT);
+        });
+    }
+
+    #[test]
+    fn hashed_deposit_covers_hash_and_locator() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::issue_cert_hashed(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                WHMIS,
+                H256::zero(),
+                Some(b"bafy".to_vec()),
+                None,
+            ));
+
+            assert_eq!(Balances::reserved_balance(ISSUER), DEPOSIT_BASE + (32 + 4) * DEPOSIT_PER_BYTE);
+        });
+    }
+
+    #[test]
+    fn delegate_issuance_reserves_from_issuer() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(INSTRUCTOR),
+                HOLDER,
+                WHMIS,
+                metadata("Jane Doe"),
+                None,
+            ));
+
+            assert_eq!(Balances::reserved_balance(ISSUER), DEPOSIT);
+            assert_eq!(Balances::reserved_balance(INSTRUCTOR), 0);
+        });
+    }
+
+    #[test]
+    fn issuance_fails_without_funds_for_deposit() {
+        build_and_execute(|| {
+            Balances::make_free_balance_be(&ISSUER, DEPOSIT - 1);
+
+            assert_noop!(
+                Certificates::issue_cert(RuntimeOrigin::signed(ISSUER), HOLDER, WHMIS, metadata("Jane Doe"), None),
+                pallet_balances::Error::<Test>::InsufficientBalance
+            );
+        });
+    }
+
+    #[test]
+    fn pruning_releases_deposit() {
+        build_and_execute(|| {
+            let revoked = issue(HOLDER, None);
+            issue(HOLDER, None);
+            assert_ok!(Certificates::revoke_cert(
+                RuntimeOrigin::signed(ISSUER),
+                revoked,
+                RevocationReason::Fraud,
+                None,
+            ));
+            assert_eq!(Balances::reserved_balance(ISSUER), 2 * DEPOSIT);
+
+            assert_ok!(Certificates::prune_certificates(RuntimeOrigin::signed(HOLDER)));
+
+            assert_eq!(Balances::reserved_balance(ISSUER), DEPOSIT);
+            assert_eq!(Certificates::certificates(revoked).unwrap().deposit, 0);
+        });
+    }
+
+    #[test]
+    fn renewal_releases_deposit_of_original() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), id, None, None));
+
+            assert_eq!(Certificates::certificates(id).unwrap().deposit, 0);
+            assert_eq!(Balances::reserved_balance(ISSUER), DEPOSIT);
+        });
+    }
+
+    #[test]
+    fn issuance_fee_goes_to_treasury() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::set_issuance_fee(RuntimeOrigin::root(), WHMIS, 5));
+            System::assert_last_event(Event::IssuanceFeeSet { type_id: WHMIS, fee: 5 }.into());
+
+            issue(HOLDER, None);
+
+            assert_eq!(Balances::free_balance(TREASURY), 5);
+            assert_eq!(Balances::free_balance(ISSUER), INITIAL_BALANCE - 5 - DEPOSIT);
+
+            // Other types stay free of charge
+            assert_ok!(Certificates::issue_cert(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                RIGGING,
+                metadata("Jane Doe"),
+                None,
+            ));
+            assert_eq!(Balances::free_balance(TREASURY), 5);
+        });
+    }
+
+    #[test]
+    fn set_issuance_fee_checks_origin_and_type() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::set_issuance_fee(RuntimeOrigin::signed(ISSUER), WHMIS, 5),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Certificates::set_issuance_fee(RuntimeOrigin::root(), 3, 5),
+                Error::<Test>::CertificateTypeNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn zero_fee_or_type_removal_clears_fee() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::set_issuance_fee(RuntimeOrigin::root(), WHMIS, 5));
+            assert_ok!(Certificates::set_issuance_fee(RuntimeOrigin::root(), WHMIS, 0));
+            assert_eq!(Certificates::issuance_fees(WHMIS), None);
+
+            assert_ok!(Certificates::set_issuance_fee(RuntimeOrigin::root(), RIGGING, 5));
+            assert_ok!(Certificates::remove_certificate_type(RuntimeOrigin::root(), RIGGING));
+            assert_eq!(Certificates::issuance_fees(RIGGING), None);
+        });
+    }
+}
+
+mod offers {
+    use super::*;
+    use crate::PendingOffers;
+
+    fn offer(holder: u64) -> u32 {
+        let id = Certificates::certificate_count();
+        assert_ok!(Certificates::offer_cert(
+            RuntimeOrigin::signed(ISSUER),
+            holder,
+            WHMIS,
+            metadata("Jane Doe"),
+            None,
+        ));
+        id
+    }
+
+    #[test]
+    fn offer_is_pending_until_accepted() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+            System::assert_last_event(
+                Event::CertificateOffered {
+                    id,
+                    owner: HOLDER,
+                    issuer: ISSUER,
+                    issued_by: ISSUER,
+                    cert_type: WHMIS,
+                    lapses_at: 1 + OFFER_PERIOD,
+                }
+                .into(),
+            );
+
+            assert_eq!(Certificates::certificate_status(id), Some(CertificateStatus::Pending));
+            assert!(!Certificates::is_certificate_valid(id));
+            assert!(Certificates::account_certificates(HOLDER).is_empty());
+            assert_eq!(Certificates::get_pending_offers(&HOLDER).len(), 1);
+            assert!(Balances::reserved_balance(ISSUER) > 0);
+        });
+    }
+
+    #[test]
+    fn holder_accepts_offer() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+
+            assert_ok!(Certificates::accept_cert(RuntimeOrigin::signed(HOLDER), id));
+            System::assert_last_event(Event::CertificateAccepted { id, owner: HOLDER }.into());
+
+            assert!(Certificates::is_certificate_valid(id));
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![id]);
+            assert!(Certificates::get_pending_offers(&HOLDER).is_empty());
+
+            // The type's validity is counted from the offer
+            let cert = Certificates::certificates(id).unwrap();
+            assert_eq!(cert.expires_at, at_block(1) + WHMIS_VALIDITY);
+            assert!(ExpiryIndex::<Test>::contains_key(cert.expires_at, id));
+        });
+    }
+
+    #[test]
+    fn only_holder_accepts_pending_offer() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+
+            assert_noop!(
+                Certificates::accept_cert(RuntimeOrigin::signed(OTHER_HOLDER), id),
+                Error::<Test>::NotCertificateOwner
+            );
+            assert_noop!(
+                Certificates::accept_cert(RuntimeOrigin::signed(HOLDER), 7),
+                Error::<Test>::CertificateNotFound
+            );
+
+            let issued = issue(HOLDER, None);
+            assert_noop!(
+                Certificates::accept_cert(RuntimeOrigin::signed(HOLDER), issued),
+                Error::<Test>::CertificateNotPending
+            );
+        });
+    }
+
+    #[test]
+    fn lapsed_offer_cannot_be_accepted() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+            run_to_block(1 + OFFER_PERIOD);
+
+            assert_noop!(
+                Certificates::accept_cert(RuntimeOrigin::signed(HOLDER), id),
+                Error::<Test>::OfferLapsed
+            );
+        });
+    }
+
+    #[test]
+    fn holder_rejects_offer() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+
+            assert_ok!(Certificates::reject_cert(RuntimeOrigin::signed(HOLDER), id));
+            System::assert_last_event(Event::CertificateRejected { id, owner: HOLDER }.into());
+
+            assert_eq!(Certificates::certificates(id), None);
+            assert!(!PendingOffers::<Test>::contains_key(HOLDER, id));
+            assert!(Certificates::get_issuer_certificates(&ISSUER, None, 10).0.is_empty());
+            assert_eq!(Balances::reserved_balance(ISSUER), 0);
+        });
+    }
+
+    #[test]
+    fn issuer_or_delegate_withdraws_offer() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            let first = offer(HOLDER);
+            let second = offer(HOLDER);
+
+            assert_ok!(Certificates::reject_cert(RuntimeOrigin::signed(ISSUER), first));
+            assert_ok!(Certificates::reject_cert(RuntimeOrigin::signed(INSTRUCTOR), second));
+
+            assert!(Certificates::get_pending_offers(&HOLDER).is_empty());
+        });
+    }
+
+    #[test]
+    fn anyone_clears_lapsed_offer() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+
+            assert_noop!(
+                Certificates::reject_cert(RuntimeOrigin::signed(OTHER_HOLDER), id),
+                Error::<Test>::NotCertificateOwner
+            );
+
+            run_to_block(1 + OFFER_PERIOD);
+            assert_ok!(Certificates::reject_cert(RuntimeOrigin::signed(OTHER_HOLDER), id));
+            assert_eq!(Balances::reserved_balance(ISSUER), 0);
+        });
+    }
+
+    #[test]
+    fn pending_offer_cannot_be_revoked() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+
+            assert_noop!(
+                Certificates::revoke_cert(RuntimeOrigin::signed(ISSUER), id, RevocationReason::IssuedInError, None),
+                Error::<Test>::CertificatePending
+            );
+            assert_noop!(
+                Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), id),
+                Error::<Test>::CertificateNotActive
+            );
+        });
+    }
+
+    #[test]
+    fn accepting_requires_room_in_account_list() {
+        build_and_execute(|| {
+            let id = offer(HOLDER);
+            for _ in 0..4 {
+                issue(HOLDER, None);
+            }
+
+            // The offer stays open while the holder makes room
+            assert_noop!(
+                Certificates::accept_cert(RuntimeOrigin::signed(HOLDER), id),
+                Error::<Test>::TooManyCertificates
+            );
+        });
+    }
+}
+
+mod burn_cert {
+    use super::*;
+    use crate::{IssuedCertificate, IssuerCertificates, Tombstone};
+
+    #[test]
+    fn holder_burns_certificate_leaving_tombstone() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            let kept = issue(HOLDER, None);
+            System::set_block_number(5);
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id));
+            System::assert_last_event(Event::CertificateBurned { id, owner: HOLDER }.into());
+
+            assert_eq!(Certificates::certificates(id), None);
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![kept]);
+            assert!(IssuerCertificates::<Test>::contains_key(ISSUER, id));
+            assert!(!ExpiryIndex::<Test>::contains_key(at_block(1) + WHMIS_VALIDITY, id));
+            assert_eq!(
+                Certificates::tombstones(id),
+                Some(Tombstone {
+                    issuer: ISSUER,
+                    content_hash: BlakeTwo256::hash(&metadata("Jane Doe")),
+                    status: CertificateStatus::Active,
+                    burned_at: 5,
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn issuer_listing_reports_burned_certificates() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            let kept = issue(HOLDER, None);
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id));
+
+            let (page, _) = Certificates::get_issuer_certificates(&ISSUER, None, 10);
+            assert_eq!(page.len(), 2);
+            assert!(page.contains(&IssuedCertificate::Stored(Certificates::certificates(kept).unwrap())));
+            assert!(page.contains(&IssuedCertificate::Burned { id, tombstone: Certificates::tombstones(id).unwrap() }));
+        });
+    }
+
+    #[test]
+    fn burning_returns_deposit_to_issuer() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert!(Balances::reserved_balance(ISSUER) > 0);
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id));
+
+            assert_eq!(Balances::reserved_balance(ISSUER), 0);
+        });
+    }
+
+    #[test]
+    fn burning_keeps_renewal_chain_linked() {
+        build_and_execute(|| {
+            let first = issue(HOLDER, None);
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), first, None, None));
+            assert_ok!(Certificates::renew_cert(RuntimeOrigin::signed(ISSUER), 1, None, None));
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), 1));
+
+            assert_eq!(Certificates::certificates(first).unwrap().superseded_by, Some(2));
+            assert_eq!(Certificates::certificates(2).unwrap().supersedes, Some(first));
+            for id in [first, 2] {
+                let chain: Vec<u32> = Certificates::get_renewal_chain(id).iter().map(|cert| cert.id).collect();
+                assert_eq!(chain, vec![first, 2]);
+            }
+        });
+    }
+
+    #[test]
+    fn tombstone_keeps_revoked_status_and_verifies_document() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::revoke_cert(
+                RuntimeOrigin::signed(ISSUER),
+                id,
+                RevocationReason::Fraud,
+                None,
+            ));
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id));
+
+            assert_eq!(Certificates::tombstones(id).unwrap().status, CertificateStatus::Revoked);
+            assert!(Certificates::verify_metadata(id, &metadata("Jane Doe")));
+            assert!(!Certificates::verify_metadata(id, &metadata("John Roe")));
+            assert!(!Certificates::is_certificate_valid(id));
+        });
+    }
+
+    #[test]
+    fn only_holder_burns() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+
+            assert_noop!(
+                Certificates::burn_cert(RuntimeOrigin::signed(ISSUER), id),
+                Error::<Test>::NotCertificateOwner
+            );
+            assert_noop!(
+                Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), 7),
+                Error::<Test>::CertificateNotFound
+            );
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id));
+            assert_noop!(
+                Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id),
+                Error::<Test>::CertificateNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn pending_offer_is_rejected_not_burned() {
+        build_and_execute(|| {
+            let id = Certificates::certificate_count();
+            assert_ok!(Certificates::offer_cert(
+                RuntimeOrigin::signed(ISSUER),
+                HOLDER,
+                WHMIS,
+                metadata("Jane Doe"),
+                None,
+            ));
+
+            assert_noop!(
+                Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id),
+                Error::<Test>::CertificatePending
+            );
+        });
+    }
+}
+
+mod accreditation {
+    use super::*;
+    use crate::{Accreditation, AccreditationRequired, TrustChain};
+
+    /// Register both accreditors and have `ACCREDITOR` accredit `ISSUER` for WHMIS until block 50
+    fn accredit_issuer() {
+        for accreditor in [ACCREDITOR, OTHER_ACCREDITOR] {
+            assert_ok!(Certificates::register_accreditor(
+                RuntimeOrigin::root(),
+                accreditor,
+                b"Safety Council".to_vec(),
+            ));
+        }
+        assert_ok!(Certificates::accredit_issuer(
+            RuntimeOrigin::signed(ACCREDITOR),
+            ISSUER,
+            WHMIS,
+            at_block(50),
+        ));
+    }
+
+    #[test]
+    fn accreditor_registry() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::register_accreditor(RuntimeOrigin::signed(ACCREDITOR), ACCREDITOR, b"Council".to_vec()),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Certificates::register_accreditor(RuntimeOrigin::root(), ACCREDITOR, vec![b'a'; 33]),
+                Error::<Test>::IssuerFieldTooLong
+            );
+
+            assert_ok!(Certificates::register_accreditor(RuntimeOrigin::root(), ACCREDITOR, b"Council".to_vec()));
+            System::assert_last_event(Event::AccreditorRegistered { accreditor: ACCREDITOR }.into());
+            let info = Certificates::accreditors(ACCREDITOR).unwrap();
+            assert_eq!(info.name.to_vec(), b"Council".to_vec());
+            assert_eq!(info.registered_at, 1);
+            assert_noop!(
+                Certificates::register_accreditor(RuntimeOrigin::root(), ACCREDITOR, b"Council".to_vec()),
+                Error::<Test>::AccreditorAlreadyRegistered
+            );
+
+            assert_ok!(Certificates::remove_accreditor(RuntimeOrigin::root(), ACCREDITOR));
+            System::assert_last_event(Event::AccreditorRemoved { accreditor: ACCREDITOR }.into());
+            assert_eq!(Certificates::accreditors(ACCREDITOR), None);
+            assert_noop!(
+                Certificates::remove_accreditor(RuntimeOrigin::root(), ACCREDITOR),
+                Error::<Test>::AccreditorNotRegistered
+            );
+        });
+    }
+
+    #[test]
+    fn accreditor_accredits_issuer_for_type() {
+        build_and_execute(|| {
+            accredit_issuer();
+
+            System::assert_last_event(
+                Event::IssuerAccredited {
+                    issuer: ISSUER,
+                    cert_type: WHMIS,
+                    accreditor: ACCREDITOR,
+                    expires_at: at_block(50),
+                }
+                .into(),
+            );
+            assert_eq!(
+                Certificates::accreditations(ISSUER, WHMIS),
+                Some(Accreditation { accreditor: ACCREDITOR, granted_at: at_block(1), expires_at: at_block(50) })
+            );
+            assert_eq!(Certificates::accreditations(ISSUER, RIGGING), None);
+        });
+    }
+
+    #[test]
+    fn accreditation_checks() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::accredit_issuer(RuntimeOrigin::signed(ACCREDITOR), ISSUER, WHMIS, at_block(50)),
+                Error::<Test>::AccreditorNotRegistered
+            );
+            accredit_issuer();
+
+            assert_noop!(
+                Certificates::accredit_issuer(RuntimeOrigin::signed(ACCREDITOR), OTHER_ISSUER, WHMIS, at_block(50)),
+                Error::<Test>::IssuerNotRegistered
+            );
+            assert_noop!(
+                Certificates::accredit_issuer(RuntimeOrigin::signed(ACCREDITOR), ISSUER, 9, at_block(50)),
+                Error::<Test>::CertificateTypeNotFound
+            );
+            assert_ok!(Certificates::register_certificate_type(
+                RuntimeOrigin::root(),
+                9,
+                b"Forklift".to_vec(),
+                0,
+                vec![],
+            ));
+            assert_noop!(
+                Certificates::accredit_issuer(RuntimeOrigin::signed(ACCREDITOR), ISSUER, 9, at_block(50)),
+                Error::<Test>::CertificateTypeNotAllowed
+            );
+            assert_noop!(
+                Certificates::accredit_issuer(RuntimeOrigin::signed(ACCREDITOR), ISSUER, RIGGING, at_block(1)),
+                Error::<Test>::InvalidAccreditationExpiry
+            );
+        });
+    }
+
+    #[test]
+    fn one_accreditor_at_a_time() {
+        build_and_execute(|| {
+            accredit_issuer();
+
+            assert_noop!(
+                Certificates::accredit_issuer(RuntimeOrigin::signed(OTHER_ACCREDITOR), ISSUER, WHMIS, at_block(80)),
+                Error::<Test>::AccreditedByAnother
+            );
+            assert_ok!(Certificates::accredit_issuer(RuntimeOrigin::signed(ACCREDITOR), ISSUER, WHMIS, at_block(60)));
+            assert_eq!(Certificates::accreditations(ISSUER, WHMIS).unwrap().expires_at, at_block(60));
+
+            // Another accreditor may take over once the accreditation has ended
+            run_to_block(60);
+            assert_ok!(Certificates::accredit_issuer(
+                RuntimeOrigin::signed(OTHER_ACCREDITOR),
+                ISSUER,
+                WHMIS,
+                at_block(80),
+            ));
+            assert_eq!(Certificates::accreditations(ISSUER, WHMIS).unwrap().accreditor, OTHER_ACCREDITOR);
+        });
+    }
+
+    #[test]
+    fn only_granting_accreditor_withdraws() {
+        build_and_execute(|| {
+            accredit_issuer();
+
+            assert_noop!(
+                Certificates::withdraw_accreditation(RuntimeOrigin::signed(OTHER_ACCREDITOR), ISSUER, WHMIS),
+                Error::<Test>::NotAccreditor
+            );
+            assert_noop!(
+                Certificates::withdraw_accreditation(RuntimeOrigin::signed(ACCREDITOR), ISSUER, RIGGING),
+                Error::<Test>::AccreditationNotFound
+            );
+
+            assert_ok!(Certificates::withdraw_accreditation(RuntimeOrigin::signed(ACCREDITOR), ISSUER, WHMIS));
+            System::assert_last_event(
+                Event::AccreditationWithdrawn { issuer: ISSUER, cert_type: WHMIS, accreditor: ACCREDITOR }.into(),
+            );
+            assert_eq!(Certificates::accreditations(ISSUER, WHMIS), None);
+        });
+    }
+
+    #[test]
+    fn issuance_records_accreditation_in_force() {
+        build_and_execute(|| {
+            let unaccredited = issue(HOLDER, None);
+            accredit_issuer();
+            let accredited = issue(HOLDER, None);
+
+            assert_eq!(Certificates::certificate_accreditations(unaccredited), None);
+            assert_eq!(
+                Certificates::certificate_accreditations(accredited),
+                Some(Accreditation { accreditor: ACCREDITOR, granted_at: at_block(1), expires_at: at_block(50) })
+            );
+
+            // Accreditations of removed accreditors no longer count
+            assert_ok!(Certificates::remove_accreditor(RuntimeOrigin::root(), ACCREDITOR));
+            let after_removal = issue(OTHER_HOLDER, None);
+            assert_eq!(Certificates::certificate_accreditations(after_removal), None);
+            assert!(Certificates::certificate_accreditations(accredited).is_some());
+
+            // Nor do ended accreditations
+            assert_ok!(Certificates::accredit_issuer(
+                RuntimeOrigin::signed(OTHER_ACCREDITOR),
+                ISSUER,
+                WHMIS,
+                at_block(5),
+            ));
+            run_to_block(5);
+            let after_expiry = issue(OTHER_HOLDER, None);
+            assert_eq!(Certificates::certificate_accreditations(after_expiry), None);
+        });
+    }
+
+    #[test]
+    fn required_accreditation_governs_validity() {
+        build_and_execute(|| {
+            let unaccredited = issue(HOLDER, None);
+            accredit_issuer();
+            let accredited = issue(HOLDER, None);
+
+            assert_noop!(
+                Certificates::set_accreditation_required(RuntimeOrigin::signed(ACCREDITOR), WHMIS, true),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Certificates::set_accreditation_required(RuntimeOrigin::root(), 9, true),
+                Error::<Test>::CertificateTypeNotFound
+            );
+            assert_ok!(Certificates::set_accreditation_required(RuntimeOrigin::root(), WHMIS, true));
+            System::assert_last_event(Event::AccreditationRequirementSet { type_id: WHMIS, required: true }.into());
+
+            assert!(!Certificates::is_certificate_valid(unaccredited));
+            assert!(Certificates::is_certificate_valid(accredited));
+
+            // Certificates issued while accredited stay valid after the accreditation ends
+            assert_ok!(Certificates::withdraw_accreditation(RuntimeOrigin::signed(ACCREDITOR), ISSUER, WHMIS));
+            assert!(Certificates::is_certificate_valid(accredited));
+            let after_withdrawal = issue(OTHER_HOLDER, None);
+            assert!(!Certificates::is_certificate_valid(after_withdrawal));
+
+            assert_ok!(Certificates::set_accreditation_required(RuntimeOrigin::root(), WHMIS, false));
+            assert!(Certificates::is_certificate_valid(unaccredited));
+            assert!(Certificates::is_certificate_valid(after_withdrawal));
+        });
+    }
+
+    #[test]
+    fn removing_type_drops_requirement() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::set_accreditation_required(RuntimeOrigin::root(), RIGGING, true));
+
+            assert_ok!(Certificates::remove_certificate_type(RuntimeOrigin::root(), RIGGING));
+
+            assert!(!AccreditationRequired::<Test>::contains_key(RIGGING));
+        });
+    }
+
+    #[test]
+    fn trust_chain_leads_to_accreditor() {
+        build_and_execute(|| {
+            let unaccredited = issue(HOLDER, None);
+            accredit_issuer();
+            let accredited = issue(HOLDER, None);
+            assert_ok!(Certificates::set_accreditation_required(RuntimeOrigin::root(), WHMIS, true));
+
+            assert_eq!(
+                Certificates::get_trust_chain(accredited),
+                Some(TrustChain {
+                    issuer: ISSUER,
+                    issuer_active: true,
+                    accreditor: Some(ACCREDITOR),
+                    accreditation_period: Some((at_block(1), at_block(50))),
+                    accreditor_registered: true,
+                    accreditation_required: true,
+                })
+            );
+            assert_eq!(
+                Certificates::get_trust_chain(unaccredited),
+                Some(TrustChain {
+                    issuer: ISSUER,
+                    issuer_active: true,
+                    accreditor: None,
+                    accreditation_period: None,
+                    accreditor_registered: false,
+                    accreditation_required: true,
+                })
+            );
+
+            assert_ok!(Certificates::remove_accreditor(RuntimeOrigin::root(), ACCREDITOR));
+            assert_ok!(Certificates::suspend_issuer(RuntimeOrigin::root(), ISSUER));
+            let chain = Certificates::get_trust_chain(accredited).unwrap();
+            assert!(!chain.issuer_active);
+            assert!(!chain.accreditor_registered);
+            assert_eq!(chain.accreditor, Some(ACCREDITOR));
+
+            assert_eq!(Certificates::get_trust_chain(7), None);
+        });
+    }
+
+    #[test]
+    fn burning_drops_accreditation_record() {
+        build_and_execute(|| {
+            accredit_issuer();
+            let id = issue(HOLDER, None);
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id));
+
+            assert_eq!(Certificates::certificate_accreditations(id), None);
+        });
+    }
+}
+
+mod regulator {
+    use super::*;
+    use crate::{CascadeAction, IssuerCertificates};
+
+    /// Number of `CertificateForceSuspended` events emitted so far
+    fn force_suspensions() -> usize {
+        System::events()
+            .iter()
+            .filter(|record| {
+                matches!(record.event, RuntimeEvent::Certificates(Event::CertificateForceSuspended { .. }))
+            })
+            .count()
+    }
+
+    #[test]
+    fn force_revoke_overrides_issuer() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            System::set_block_number(5);
+
+            assert_noop!(
+                Certificates::force_revoke(RuntimeOrigin::signed(ISSUER), id, RevocationReason::Fraud, None),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Certificates::force_revoke(RuntimeOrigin::root(), id, RevocationReason::Fraud, None));
+
+            System::assert_last_event(
+                Event::CertificateForceRevoked { id, reason: RevocationReason::Fraud, note_hash: None, revoked_at: 5 }
+                    .into(),
+            );
+            let cert = Certificates::certificates(id).unwrap();
+            assert_eq!(cert.status, CertificateStatus::Revoked);
+            assert_eq!(
+                cert.revocation,
+                Some(RevocationInfo { reason: RevocationReason::Fraud, note_hash: None, revoked_at: 5 })
+            );
+            assert!(!ExpiryIndex::<Test>::contains_key(at_block(1) + WHMIS_VALIDITY, id));
+        });
+    }
+
+    #[test]
+    fn force_revoke_checks_certificate() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            let offered = Certificates::certificate_count();
+            assert_ok!(Certificates::offer_cert(
+                RuntimeOrigin::signed(ISSUER),
+                OTHER_HOLDER,
+                WHMIS,
+                metadata("John Roe"),
+                None,
+            ));
+
+            assert_noop!(
+                Certificates::force_revoke(RuntimeOrigin::root(), 7, RevocationReason::Fraud, None),
+                Error::<Test>::CertificateNotFound
+            );
+            assert_noop!(
+                Certificates::force_revoke(RuntimeOrigin::root(), offered, RevocationReason::Fraud, None),
+                Error::<Test>::CertificatePending
+            );
+            assert_ok!(Certificates::force_revoke(RuntimeOrigin::root(), id, RevocationReason::Fraud, None));
+            assert_noop!(
+                Certificates::force_revoke(RuntimeOrigin::root(), id, RevocationReason::Fraud, None),
+                Error::<Test>::CertificateAlreadyRevoked
+            );
+        });
+    }
+
+    #[test]
+    fn force_suspend_issuer_cascades_over_blocks() {
+        build_and_execute(|| {
+            let ids: Vec<u32> = (20..25).map(|holder| issue(holder, None)).collect();
+
+            assert_noop!(
+                Certificates::force_suspend_issuer(RuntimeOrigin::signed(ISSUER), ISSUER, CascadeAction::Suspend),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Certificates::force_suspend_issuer(RuntimeOrigin::root(), ISSUER, CascadeAction::Suspend));
+
+            System::assert_last_event(
+                Event::IssuerForceSuspended { issuer: ISSUER, action: CascadeAction::Suspend }.into(),
+            );
+            assert_eq!(Certificates::issuers(ISSUER).unwrap().status, IssuerStatus::Suspended);
+            assert!(Certificates::cascades(ISSUER).is_some());
+
+            // Two certificates are processed per block
+            run_to_block(2);
+            assert_eq!(force_suspensions(), 2);
+            assert!(Certificates::cascades(ISSUER).is_some());
+
+            run_to_block(4);
+            assert_eq!(force_suspensions(), 5);
+            System::assert_last_event(Event::CascadeCompleted { issuer: ISSUER }.into());
+            assert_eq!(Certificates::cascades(ISSUER), None);
+            for id in ids {
+                assert_eq!(Certificates::certificate_status(id), Some(CertificateStatus::ForceSuspended));
+            }
+        });
+    }
+
+    #[test]
+    fn only_regulator_reinstates_its_suspensions() {
+        build_and_execute(|| {
+            let force_suspended = issue(HOLDER, None);
+            assert_ok!(Certificates::force_suspend_issuer(RuntimeOrigin::root(), ISSUER, CascadeAction::Suspend));
+            run_to_block(2);
+            assert_ok!(Certificates::reinstate_issuer(RuntimeOrigin::root(), ISSUER));
+            let suspended = issue(HOLDER, None);
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), suspended));
+
+            assert_noop!(
+                Certificates::reinstate_cert(RuntimeOrigin::signed(ISSUER), force_suspended),
+                Error::<Test>::SuspendedByRegulator
+            );
+            assert_ok!(Certificates::reinstate_cert(RuntimeOrigin::root(), force_suspended));
+            System::assert_last_event(Event::CertificateReinstated { id: force_suspended }.into());
+            assert_eq!(Certificates::certificate_status(force_suspended), Some(CertificateStatus::Active));
+
+            // The regulator may also reinstate certificates the issuer suspended
+            assert_ok!(Certificates::reinstate_cert(RuntimeOrigin::root(), suspended));
+            assert_noop!(
+                Certificates::reinstate_cert(RuntimeOrigin::root(), suspended),
+                Error::<Test>::CertificateNotSuspended
+            );
+        });
+    }
+
+    #[test]
+    fn force_suspend_issuer_checks_account() {
+        build_and_execute(|| {
+            assert_noop!(
+                Certificates::force_suspend_issuer(RuntimeOrigin::root(), HOLDER, CascadeAction::Suspend),
+                Error::<Test>::IssuerNotRegistered
+            );
+
+            // A removed issuer without certificates has nothing to cascade
+            assert_ok!(Certificates::remove_issuer(RuntimeOrigin::root(), ISSUER));
+            assert_noop!(
+                Certificates::force_suspend_issuer(RuntimeOrigin::root(), ISSUER, CascadeAction::Suspend),
+                Error::<Test>::IssuerNotRegistered
+            );
+        });
+    }
+
+    #[test]
+    fn revoke_cascade_spares_certificates_no_longer_in_force() {
+        build_and_execute(|| {
+            let active = issue(HOLDER, None);
+            let suspended = issue(HOLDER, None);
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), suspended));
+            let revoked = issue(HOLDER, None);
+            assert_ok!(Certificates::revoke_cert(
+                RuntimeOrigin::signed(ISSUER),
+                revoked,
+                RevocationReason::IssuedInError,
+                None,
+            ));
+            let offered = Certificates::certificate_count();
+            assert_ok!(Certificates::offer_cert(
+                RuntimeOrigin::signed(ISSUER),
+                OTHER_HOLDER,
+                WHMIS,
+                metadata("John Roe"),
+                None,
+            ));
+            let reserved = Balances::reserved_balance(ISSUER);
+
+            assert_ok!(Certificates::force_suspend_issuer(
+                RuntimeOrigin::root(),
+                ISSUER,
+                CascadeAction::Revoke(RevocationReason::Fraud),
+            ));
+            run_to_block(3);
+
+            assert_eq!(Certificates::cascades(ISSUER), None);
+            for id in [active, suspended] {
+                let cert = Certificates::certificates(id).unwrap();
+                assert_eq!(cert.status, CertificateStatus::Revoked);
+                assert_eq!(cert.revocation.unwrap().reason, RevocationReason::Fraud);
+            }
+            assert_eq!(
+                Certificates::certificates(revoked).unwrap().revocation.unwrap().reason,
+                RevocationReason::IssuedInError
+            );
+
+            // The pending offer is withdrawn and its deposit returned
+            assert_eq!(Certificates::certificates(offered), None);
+            assert!(!IssuerCertificates::<Test>::contains_key(ISSUER, offered));
+            assert!(Balances::reserved_balance(ISSUER) < reserved);
+        });
+    }
+
+    #[test]
+    fn force_suspend_issuer_cascades_removed_issuer() {
+        build_and_execute(|| {
+            let ids: Vec<u32> = (20..22).map(|holder| issue(holder, None)).collect();
+            assert_ok!(Certificates::remove_issuer(RuntimeOrigin::root(), ISSUER));
+
+            assert_ok!(Certificates::force_suspend_issuer(
+                RuntimeOrigin::root(),
+                ISSUER,
+                CascadeAction::Revoke(RevocationReason::Fraud),
+            ));
+            assert_eq!(Certificates::issuers(ISSUER), None);
+            run_to_block(2);
+
+            System::assert_last_event(Event::CascadeCompleted { issuer: ISSUER }.into());
+            for id in ids {
+                assert_eq!(Certificates::certificate_status(id), Some(CertificateStatus::Revoked));
+            }
+        });
+    }
+
+    #[test]
+    fn new_action_replaces_cascade_in_progress() {
+        build_and_execute(|| {
+            let ids: Vec<u32> = (20..24).map(|holder| issue(holder, None)).collect();
+            assert_ok!(Certificates::force_suspend_issuer(RuntimeOrigin::root(), ISSUER, CascadeAction::Suspend));
+            run_to_block(2);
+
+            assert_ok!(Certificates::force_suspend_issuer(
+                RuntimeOrigin::root(),
+                ISSUER,
+                CascadeAction::Revoke(RevocationReason::Fraud),
+            ));
+            run_to_block(4);
+
+            for id in ids {
+                assert_eq!(Certificates::certificate_status(id), Some(CertificateStatus::Revoked));
+            }
+        });
+    }
+}
+
+mod endorsements {
+    use super::*;
+    use crate::{Endorsement, Endorsements};
+
+    fn claim(text: &str) -> H256 {
+        BlakeTwo256::hash(text.as_bytes())
+    }
+
+    #[test]
+    fn endorser_attests_claim() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            System::set_block_number(5);
+
+            assert_ok!(Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), id, claim("500h crane")));
+
+            System::assert_last_event(
+                Event::CertificateEndorsed { id, endorser: ENDORSER, claim_hash: claim("500h crane") }.into(),
+            );
+            assert_eq!(
+                Certificates::get_endorsements(id),
+                vec![Endorsement {
+                    endorser: ENDORSER,
+                    claim_hash: claim("500h crane"),
+                    endorsed_at: 5,
+                    deposit: ENDORSEMENT_DEPOSIT,
+                }]
+            );
+            assert_eq!(Balances::reserved_balance(ENDORSER), ENDORSEMENT_DEPOSIT);
+        });
+    }
+
+    #[test]
+    fn endorsement_checks() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+
+            assert_noop!(
+                Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), 7, claim("500h crane")),
+                Error::<Test>::CertificateNotFound
+            );
+            assert_noop!(
+                Certificates::endorse_cert(RuntimeOrigin::signed(HOLDER), id, claim("500h crane")),
+                Error::<Test>::SelfEndorsement
+            );
+            assert_noop!(
+                Certificates::endorse_cert(RuntimeOrigin::signed(OTHER_HOLDER), id, claim("500h crane")),
+                pallet_balances::Error::<Test>::InsufficientBalance
+            );
+
+            assert_ok!(Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), id, claim("500h crane")));
+            assert_noop!(
+                Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), id, claim("500h crane")),
+                Error::<Test>::AlreadyEndorsed
+            );
+            assert_ok!(Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), id, claim("night lifts")));
+            assert_noop!(
+                Certificates::endorse_cert(RuntimeOrigin::signed(OTHER_ENDORSER), id, claim("500h crane")),
+                Error::<Test>::TooManyEndorsements
+            );
+        });
+    }
+
+    #[test]
+    fn only_active_certificates_are_endorsed() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::suspend_cert(RuntimeOrigin::signed(ISSUER), id));
+
+            assert_noop!(
+                Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), id, claim("500h crane")),
+                Error::<Test>::CertificateNotActive
+            );
+        });
+    }
+
+    #[test]
+    fn endorser_revokes_and_holder_dismisses() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), id, claim("500h crane")));
+            assert_ok!(Certificates::endorse_cert(RuntimeOrigin::signed(OTHER_ENDORSER), id, claim("night lifts")));
+
+            assert_noop!(
+                Certificates::revoke_endorsement(RuntimeOrigin::signed(ISSUER), id, ENDORSER, claim("500h crane")),
+                Error::<Test>::NotCertificateOwner
+            );
+            assert_noop!(
+                Certificates::revoke_endorsement(RuntimeOrigin::signed(ENDORSER), id, ENDORSER, claim("night lifts")),
+                Error::<Test>::EndorsementNotFound
+            );
+
+            assert_ok!(Certificates::revoke_endorsement(
+                RuntimeOrigin::signed(ENDORSER),
+                id,
+                ENDORSER,
+                claim("500h crane"),
+            ));
+            System::assert_last_event(
+                Event::EndorsementRevoked { id, endorser: ENDORSER, claim_hash: claim("500h crane") }.into(),
+            );
+            assert_eq!(Balances::reserved_balance(ENDORSER), 0);
+
+            assert_ok!(Certificates::revoke_endorsement(
+                RuntimeOrigin::signed(HOLDER),
+                id,
+                OTHER_ENDORSER,
+                claim("night lifts"),
+            ));
+            assert_eq!(Balances::reserved_balance(OTHER_ENDORSER), 0);
+            assert!(Certificates::get_endorsements(id).is_empty());
+            assert!(!Endorsements::<Test>::contains_key(id));
+        });
+    }
+
+    #[test]
+    fn burning_drops_endorsements() {
+        build_and_execute(|| {
+            let id = issue(HOLDER, None);
+            assert_ok!(Certificates::endorse_cert(RuntimeOrigin::signed(ENDORSER), id, claim("500h crane")));
+
+            assert_ok!(Certificates::burn_cert(RuntimeOrigin::signed(HOLDER), id));
+
+            assert!(Certificates::get_endorsements(id).is_empty());
+            assert_eq!(Balances::reserved_balance(ENDORSER), 0);
+        });
+    }
+}
+
+mod vouchers {
+    use super::*;
+    use crate::{NonceExpiries, UsedNonces, Voucher};
+    use codec::Encode;
+    use sp_runtime::testing::TestSignature;
+
+    fn voucher(recipient: u64, nonce: u64) -> Voucher<Test> {
+        Voucher {
+            issuer: ISSUER,
+            recipient,
+            cert_type: WHMIS,
+            metadata: BoundedVec::truncate_from(metadata("Jane Doe")),
+            expires_at: Some(at_block(50)),
+            nonce,
+            valid_until: at_block(10),
+        }
+    }
+
+    #[test]
+    fn holder_claims_certificate() {
+        build_and_execute(|| {
+            let voucher = voucher(HOLDER, 7);
+            let signature = sign_voucher(ISSUER, &voucher);
+
+            assert_ok!(Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher, ISSUER, signature));
+
+            let cert = Certificates::certificates(0).unwrap();
+            assert_eq!(cert.owner, HOLDER);
+            assert_eq!(cert.issuer, ISSUER);
+            assert_eq!(cert.issued_by, ISSUER);
+            assert_eq!(cert.expires_at, at_block(50));
+            assert_eq!(cert.status, CertificateStatus::Active);
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![0]);
+            assert_eq!(Balances::reserved_balance(ISSUER), cert.deposit);
+            assert_eq!(UsedNonces::<Test>::get(ISSUER, 7), Some(at_block(10)));
+
+            System::assert_last_event(
+                Event::CertificateClaimed {
+                    id: 0,
+                    owner: HOLDER,
+                    issuer: ISSUER,
+                    issued_by: ISSUER,
+                    cert_type: WHMIS,
+                    nonce: 7,
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn voucher_signed_by_delegate() {
+        build_and_execute(|| {
+            assert_ok!(Certificates::add_delegate(RuntimeOrigin::signed(ISSUER), INSTRUCTOR));
+            let voucher = voucher(OTHER_HOLDER, 1);
+            let signature = sign_voucher(INSTRUCTOR, &voucher);
+
+            assert_ok!(Certificates::claim_cert(RuntimeOrigin::signed(OTHER_HOLDER), voucher, INSTRUCTOR, signature));
+
+            let cert = Certificates::certificates(0).unwrap();
+            assert_eq!(cert.owner, OTHER_HOLDER);
+            assert_eq!(cert.issuer, ISSUER);
+            assert_eq!(cert.issued_by, INSTRUCTOR);
+        });
+    }
+
+    #[test]
+    fn rejects_invalid_signature() {
+        build_and_execute(|| {
+            let voucher = voucher(HOLDER, 1);
+
+            let forged = sign_voucher(OTHER_ISSUER, &voucher);
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher.clone(), ISSUER, forged),
+                Error::<Test>::InvalidVoucherSignature
+            );
+
+            let mut tampered = voucher.clone();
+            tampered.expires_at = None;
+            assert_noop!(
+                Certificates::claim_cert(
+                    RuntimeOrigin::signed(HOLDER),
+                    tampered,
+                    ISSUER,
+                    sign_voucher(ISSUER, &voucher)
+                ),
+                Error::<Test>::InvalidVoucherSignature
+            );
+
+            // A signature that does not commit to the chain's genesis hash is refused
+            let unbound = TestSignature(ISSUER, voucher.encode());
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher.clone(), ISSUER, unbound),
+                Error::<Test>::InvalidVoucherSignature
+            );
+
+            let signature = sign_voucher(OTHER_ISSUER, &voucher);
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher, OTHER_ISSUER, signature),
+                Error::<Test>::SignerNotIssuer
+            );
+        });
+    }
+
+    #[test]
+    fn checks_recipient_and_validity() {
+        build_and_execute(|| {
+            let voucher = voucher(HOLDER, 1);
+            let signature = sign_voucher(ISSUER, &voucher);
+            assert_noop!(
+                Certificates::claim_cert(
+                    RuntimeOrigin::signed(OTHER_HOLDER),
+                    voucher.clone(),
+                    ISSUER,
+                    signature.clone()
+                ),
+                Error::<Test>::NotVoucherRecipient
+            );
+
+            run_to_block(10);
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher, ISSUER, signature),
+                Error::<Test>::VoucherExpired
+            );
+        });
+    }
+
+    #[test]
+    fn nonce_is_claimed_once() {
+        build_and_execute(|| {
+            let voucher = voucher(HOLDER, 1);
+            let signature = sign_voucher(ISSUER, &voucher);
+            assert_ok!(Certificates::claim_cert(
+                RuntimeOrigin::signed(HOLDER),
+                voucher.clone(),
+                ISSUER,
+                signature.clone()
+            ));
+
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher.clone(), ISSUER, signature),
+                Error::<Test>::VoucherAlreadyUsed
+            );
+
+            let mut next = voucher;
+            next.nonce = 2;
+            let signature = sign_voucher(ISSUER, &next);
+            assert_ok!(Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), next, ISSUER, signature));
+            assert_eq!(Certificates::certificate_count(), 2);
+        });
+    }
+
+    #[test]
+    fn failed_issuance_leaves_nonce_unused() {
+        build_and_execute(|| {
+            let mut voucher = voucher(HOLDER, 1);
+            voucher.metadata = BoundedVec::truncate_from(b"{}".to_vec());
+            let signature = sign_voucher(ISSUER, &voucher);
+
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher, ISSUER, signature),
+                Error::<Test>::MissingMetadataField
+            );
+            assert!(!UsedNonces::<Test>::contains_key(ISSUER, 1));
+        });
+    }
+
+    #[test]
+    fn rejects_validity_beyond_limit() {
+        build_and_execute(|| {
+            let mut voucher = voucher(HOLDER, 1);
+            voucher.valid_until = Certificates::now() + MAX_VOUCHER_VALIDITY + 1;
+            let signature = sign_voucher(ISSUER, &voucher);
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher.clone(), ISSUER, signature),
+                Error::<Test>::VoucherValidityTooLong
+            );
+
+            voucher.valid_until -= 1;
+            let signature = sign_voucher(ISSUER, &voucher);
+            assert_ok!(Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher, ISSUER, signature));
+        });
+    }
+
+    #[test]
+    fn nonce_is_pruned_once_voucher_lapses() {
+        build_and_execute(|| {
+            let voucher = voucher(HOLDER, 1);
+            let signature = sign_voucher(ISSUER, &voucher);
+            assert_ok!(Certificates::claim_cert(
+                RuntimeOrigin::signed(HOLDER),
+                voucher.clone(),
+                ISSUER,
+                signature.clone()
+            ));
+            assert!(NonceExpiries::<Test>::contains_key(at_block(10), (ISSUER, 1)));
+
+            run_to_block(9);
+            assert!(UsedNonces::<Test>::contains_key(ISSUER, 1));
+
+            run_to_block(10);
+            assert!(!UsedNonces::<Test>::contains_key(ISSUER, 1));
+            assert_eq!(NonceExpiries::<Test>::iter().count(), 0);
+
+            // The lapsed voucher still cannot be claimed again
+            assert_noop!(
+                Certificates::claim_cert(RuntimeOrigin::signed(HOLDER), voucher, ISSUER, signature),
+                Error::<Test>::VoucherExpired
+            );
+        });
+    }
+}
+
+mod queries {
+    use super::*;
+    use crate::IssuedCertificate;
+
+    #[test]
+    fn issuer_certificates_are_paginated() {
+        build_and_execute(|| {
+            for holder in 20..25 {
+                issue(holder, None);
+            }
+
+            let mut seen = Vec::new();
+            let mut cursor = None;
+            loop {
+                let (page, next) = Certificates::get_issuer_certificates(&ISSUER, cursor, 2);
+                assert!(page.len() <= 2);
+                seen.extend(page.iter().map(IssuedCertificate::id));
+                match next {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            seen.sort();
+            assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+
+            let (page, next) = Certificates::get_issuer_certificates(&OTHER_ISSUER, None, 10);
+            assert!(page.is_empty());
+            assert_eq!(next, None);
+        });
+    }
+
+    #[test]
+    fn validity_of_missing_certificate() {
+        build_and_execute(|| {
+            assert!(!Certificates::is_certificate_valid(0));
+            assert_eq!(Certificates::certificate_status(0), None);
+            assert!(Certificates::get_renewal_chain(0).is_empty());
+        });
+    }
+}
+
+#[test]
+fn integrity_test_passes() {
+    new_test_ext().execute_with(|| {
+        <Certificates as Hooks<u64>>::integrity_test();
+    });
+}
+
+#[test]
+fn try_state_detects_dangling_account_entry() {
+    new_test_ext().execute_with(|| {
+        AccountCertificates::<Test>::insert(HOLDER, BoundedVec::truncate_from(vec![7]));
+        assert!(Certificates::do_try_state().is_err());
+    });
+}
+
+mod migrations {
+    use super::*;
+    use crate::{
+        migrations::{v1, v2, v2::MigrateToV2, v3::MigrateToV3},
+        CertificateTypes, IssuerCertificates, SiteCount, SiteProfiles,
+    };
+    use frame_support::{
+        assert_storage_noop,
+        storage::unhashed,
+        traits::{ConstU32, ConstU64, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    };
+
+    type Migration = MigrateToV2<Test, ConstU32<{ RIGGING }>>;
+    type MigrationV3 = MigrateToV3<Test, ConstU64<MILLISECS_PER_BLOCK>>;
+
+    fn put_v1_certificate(id: u32, owner: u64, revoked: bool, expires_at: u64) {
+        let cert = v1::Certificate::<Test> {
+            id,
+            owner,
+            issuer: ISSUER,
+            metadata: BoundedVec::truncate_from(metadata("Jane Doe")),
+            issued_at: 1,
+            revoked,
+            expires_at,
+        };
+        unhashed::put(&crate::Certificates::<Test>::hashed_key_for(id), &cert);
+        CertificateCount::<Test>::mutate(|count| *count = (*count).max(id + 1));
+    }
+
+    fn put_v1_account_list(account: u64, ids: Vec<u32>) {
+        unhashed::put(&AccountCertificates::<Test>::hashed_key_for(account), &ids);
+    }
+
+    #[test]
+    fn migrates_v1_certificates() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(1).put::<Certificates>();
+            put_v1_certificate(0, HOLDER, false, 50);
+            put_v1_certificate(1, HOLDER, true, 50);
+            put_v1_certificate(2, OTHER_HOLDER, false, 0);
+            put_v1_account_list(HOLDER, vec![0, 1]);
+            put_v1_account_list(OTHER_HOLDER, vec![2]);
+
+            Migration::on_runtime_upgrade();
+
+            assert_eq!(Certificates::on_chain_storage_version(), 2);
+
+            let active = v2::Certificates::<Test>::get(0).unwrap();
+            assert_eq!(active.owner, HOLDER);
+            assert_eq!(active.issuer, ISSUER);
+            assert_eq!(active.issued_by, ISSUER);
+            assert_eq!(active.cert_type, RIGGING);
+            assert_eq!(active.content, CertificateContent::Inline(BoundedVec::truncate_from(metadata("Jane Doe"))));
+            assert_eq!(active.issued_at, 1);
+            assert_eq!(active.status, CertificateStatus::Active);
+            assert_eq!(active.expires_at, 50);
+            assert_eq!(active.supersedes, None);
+
+            let revoked = v2::Certificates::<Test>::get(1).unwrap();
+            assert_eq!(revoked.status, CertificateStatus::Revoked);
+            assert_eq!(revoked.revocation, None);
+
+            // Only certificates in force with an expiry are indexed
+            assert_eq!(v2::ExpiryIndex::<Test>::iter_keys().collect::<Vec<_>>(), vec![(50, 0)]);
+            assert_eq!(IssuerCertificates::<Test>::iter_prefix(ISSUER).count(), 3);
+
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![0, 1]);
+            assert_eq!(Certificates::account_certificates(OTHER_HOLDER).to_vec(), vec![2]);
+        });
+    }
+
+    #[test]
+    fn truncates_long_account_lists() {
+        build_and_execute(|| {
+            StorageVersion::new(1).put::<Certificates>();
+            for id in 0..6 {
+                put_v1_certificate(id, HOLDER, id == 0, 0);
+            }
+            put_v1_account_list(HOLDER, (0..6).collect());
+
+            Migration::on_runtime_upgrade();
+
+            // The revoked certificate goes first, then the oldest active one
+            assert_eq!(Certificates::account_certificates(HOLDER).to_vec(), vec![2, 3, 4, 5]);
+            assert!(Certificates::certificates(1).is_some());
+        });
+    }
+
+    #[cfg(feature = "try-runtime")]
+    #[test]
+    fn pre_upgrade_refuses_long_account_lists() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(1).put::<Certificates>();
+            for id in 0..5 {
+                put_v1_certificate(id, HOLDER, false, 0);
+            }
+            put_v1_account_list(HOLDER, (0..4).collect());
+            assert!(Migration::pre_upgrade().is_ok());
+
+            put_v1_account_list(HOLDER, (0..5).collect());
+            assert!(Migration::pre_upgrade().is_err());
+        });
+    }
+
+    #[test]
+    fn skips_when_already_migrated() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(2).put::<Certificates>();
+            put_v1_account_list(HOLDER, vec![0, 1]);
+
+            assert_storage_noop!(Migration::on_runtime_upgrade());
+        });
+    }
+
+    fn put_v2_certificate(id: u32, status: CertificateStatus, expires_at: u64) {
+        v2::Certificates::<Test>::insert(
+            id,
+            v2::Certificate::<Test> {
+                id,
+                owner: HOLDER,
+                issuer: ISSUER,
+                issued_by: ISSUER,
+                cert_type: WHMIS,
+                content: CertificateContent::Inline(BoundedVec::truncate_from(metadata("Jane Doe"))),
+                deposit: 0,
+                issued_at: 1,
+                status,
+                revocation: None,
+                expires_at,
+                supersedes: None,
+                superseded_by: None,
+            },
+        );
+        if status == CertificateStatus::Active && expires_at > 0 {
+            v2::ExpiryIndex::<Test>::insert(expires_at, id, ());
+        }
+        IssuerCertificates::<Test>::insert(ISSUER, id, ());
+        CertificateCount::<Test>::mutate(|count| *count = (*count).max(id + 1));
+    }
+
+    #[test]
+    fn migrates_v2_block_expiries_to_time() {
+        build_and_execute(|| {
+            StorageVersion::new(2).put::<Certificates>();
+            run_to_block(10);
+            put_v2_certificate(0, CertificateStatus::Active, 50);
+            put_v2_certificate(1, CertificateStatus::Active, 5);
+            put_v2_certificate(2, CertificateStatus::Active, 0);
+            put_v2_certificate(3, CertificateStatus::Revoked, 50);
+            unhashed::put(
+                &CertificateTypes::<Test>::hashed_key_for(WHMIS),
+                &v2::CertificateTypeInfo::<Test> {
+                    name: BoundedVec::truncate_from(b"WHMIS 2015".to_vec()),
+                    default_validity: 100,
+                    required_fields: BoundedVec::truncate_from(vec![BoundedVec::truncate_from(b"name".to_vec())]),
+                },
+            );
+            unhashed::put(
+                &SiteProfiles::<Test>::hashed_key_for(0),
+                &v2::SiteProfile::<Test> {
+                    owner: HOLDER,
+                    name: BoundedVec::truncate_from(b"Harbour Tower".to_vec()),
+                    requirements: BoundedVec::truncate_from(vec![v2::SiteRequirement {
+                        cert_type: WHMIS,
+                        min_remaining_validity: 10,
+                    }]),
+                },
+            );
+            SiteCount::<Test>::put(1);
+
+            MigrationV3::on_runtime_upgrade();
+
+            assert_eq!(Certificates::on_chain_storage_version(), 3);
+
+            // Expiries keep their distance from the current block
+            assert_eq!(Certificates::certificates(0).unwrap().expires_at, at_block(50));
+            assert_eq!(Certificates::certificates(1).unwrap().expires_at, at_block(5));
+            assert_eq!(Certificates::certificates(2).unwrap().expires_at, 0);
+            assert_eq!(Certificates::certificates(3).unwrap().expires_at, at_block(50));
+            assert!(Certificates::is_certificate_valid(0));
+            assert!(!Certificates::is_certificate_valid(1));
+            assert!(Certificates::is_certificate_valid(2));
+
+            // The index is rebuilt by time for certificates in force only
+            assert_eq!(ExpiryIndex::<Test>::iter_keys().collect::<Vec<_>>(), vec![(at_block(50), 0)]);
+            assert_eq!(ExpiryCursor::<Test>::get(), Some(at_block(10)));
+
+            assert_eq!(Certificates::certificate_types(WHMIS).unwrap().default_validity, WHMIS_VALIDITY);
+            assert_eq!(
+                Certificates::site_profiles(0).unwrap().requirements[0].min_remaining_validity,
+                10 * MILLISECS_PER_BLOCK
+            );
+        });
+    }
+
+    #[test]
+    fn v3_skips_unless_at_version_2() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(3).put::<Certificates>();
+            put_v2_certificate(0, CertificateStatus::Active, 50);
+
+            assert_storage_noop!(MigrationV3::on_runtime_upgrade());
+
+            StorageVersion::new(1).put::<Certificates>();
+            assert_storage_noop!(MigrationV3::on_runtime_upgrade());
+        });
+    }
+}
@@ -0,0 +1,1011 @@
+//! Weights for pallet_certificates
+//!
+//! Storage reads and writes follow the benchmarks in `benchmarking.rs`. Execution times are
+//! estimates until this file is regenerated on reference hardware, which should also be done
+//! after changing any extrinsic:
+//!
+//! ./target/release/verisite-node benchmark pallet \
+//!     --chain=dev \
+//!     --steps=50 \
+//!     --repeat=20 \
+//!     --pallet=pallet_certificates \
+//!     --extrinsic=* \
+//!     --execution=wasm \
+//!     --wasm-execution=compiled \
+//!     --output=pallets/certificates/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_certificates.
+pub trait WeightInfo {
+	fn issue_cert(m: u32, ) -> Weight;
+	fn revoke_cert() -> Weight;
+	fn suspend_cert() -> Weight;
+	fn reinstate_cert() -> Weight;
+	fn renew_cert(m: u32, ) -> Weight;
+	fn register_issuer(t: u32, ) -> Weight;
+	fn suspend_issuer() -> Weight;
+	fn reinstate_issuer() -> Weight;
+	fn remove_issuer(t: u32, d: u32, ) -> Weight;
+	fn register_certificate_type(f: u32, ) -> Weight;
+	fn remove_certificate_type() -> Weight;
+	fn issue_batch(n: u32, ) -> Weight;
+	fn prune_certificates(c: u32, ) -> Weight;
+	fn issue_cert_hashed() -> Weight;
+	fn set_prerequisites(p: u32, ) -> Weight;
+	fn create_site_profile(r: u32, ) -> Weight;
+	fn update_site_profile(r: u32, ) -> Weight;
+	fn remove_site_profile() -> Weight;
+	fn add_delegate() -> Weight;
+	fn remove_delegate() -> Weight;
+	fn rotate_issuer_key() -> Weight;
+	fn set_issuance_fee() -> Weight;
+	fn offer_cert(m: u32, ) -> Weight;
+	fn accept_cert() -> Weight;
+	fn reject_cert() -> Weight;
+	fn burn_cert(e: u32, ) -> Weight;
+	fn register_accreditor() -> Weight;
+	fn remove_accreditor() -> Weight;
+	fn accredit_issuer() -> Weight;
+	fn withdraw_accreditation() -> Weight;
+	fn set_accreditation_required() -> Weight;
+	fn force_revoke() -> Weight;
+	fn force_suspend_issuer() -> Weight;
+	fn process_cascade(c: u32, ) -> Weight;
+	fn endorse_cert() -> Weight;
+	fn revoke_endorsement() -> Weight;
+	fn claim_cert(m: u32, ) -> Weight;
+	fn check_prerequisites(c: u32, ) -> Weight;
+	fn process_expiries(b: u32, e: u32, ) -> Weight;
+	fn process_nonce_expiries(b: u32, n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_certificates using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn issue_cert(m: u32, ) -> Weight {
+		Weight::from_parts(32_410_000, 4_120)
+			.saturating_add(Weight::from_parts(1_215, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	fn revoke_cert() -> Weight {
+		Weight::from_parts(21_530_000, 3_780)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Certificates (r:1 w:1)
+	fn suspend_cert() -> Weight {
+		Weight::from_parts(18_940_000, 3_780)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates Certificates (r:1 w:1)
+	fn reinstate_cert() -> Weight {
+		Weight::from_parts(22_170_000, 3_930)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Certificates (r:3 w:2)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:2)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn renew_cert(m: u32, ) -> Weight {
+		Weight::from_parts(46_860_000, 7_540)
+			.saturating_add(Weight::from_parts(1_230, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:1)
+	/// Storage: Certificates CertificateTypes (r:t w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	fn register_issuer(t: u32, ) -> Weight {
+		Weight::from_parts(18_940_000, 3_930)
+			.saturating_add(Weight::from_parts(2_910_000, 2_520).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+	}
+	/// Storage: Certificates Issuers (r:1 w:1)
+	fn suspend_issuer() -> Weight {
+		Weight::from_parts(16_880_000, 3_930)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Issuers (r:1 w:1)
+	fn reinstate_issuer() -> Weight {
+		Weight::from_parts(16_910_000, 3_930)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Issuers (r:1 w:1)
+	/// Storage: Certificates Accreditations (r:0 w:t)
+	/// Storage: Certificates IssuerDelegates (r:1 w:1)
+	/// Storage: Certificates Delegates (r:0 w:d)
+	/// Storage: Certificates IssuerKeys (r:1 w:1)
+	/// Storage: Certificates SigningKeys (r:0 w:1)
+	/// Storage: Certificates RetiredKeys (r:0 w:2)
+	fn remove_issuer(t: u32, d: u32, ) -> Weight {
+		Weight::from_parts(24_610_000, 9_840)
+			.saturating_add(Weight::from_parts(1_870_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_920_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:1)
+	fn register_certificate_type(f: u32, ) -> Weight {
+		Weight::from_parts(15_630_000, 3_520)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:1)
+	/// Storage: Certificates Prerequisites (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:0 w:1)
+	/// Storage: Certificates AccreditationRequired (r:0 w:1)
+	fn remove_certificate_type() -> Weight {
+		Weight::from_parts(15_210_000, 3_520)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:n w:0)
+	/// Storage: Certificates CertificateTypes (r:n w:0)
+	/// Storage: Certificates Prerequisites (r:n w:0)
+	/// Storage: Certificates AccountCertificates (r:n w:n)
+	/// Storage: Certificates Certificates (r:0 w:n)
+	/// Storage: Certificates CertificateCount (r:n w:n)
+	/// Storage: Certificates IssuerCertificates (r:0 w:n)
+	/// Storage: Certificates ExpiryIndex (r:0 w:n)
+	/// Storage: Certificates IssuanceFees (r:n w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:n w:0)
+	/// Storage: Certificates Accreditors (r:n w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:n)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn issue_batch(n: u32, ) -> Weight {
+		Weight::from_parts(9_870_000, 990)
+			.saturating_add(Weight::from_parts(35_650_000, 4_120).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:c w:c)
+	/// Storage: System Account (r:c w:c)
+	fn prune_certificates(c: u32, ) -> Weight {
+		Weight::from_parts(14_020_000, 1_520)
+			.saturating_add(Weight::from_parts(5_380_000, 3_780).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(c.into())))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn issue_cert_hashed() -> Weight {
+		Weight::from_parts(27_870_000, 4_120)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:p w:0)
+	/// Storage: Certificates Prerequisites (r:0 w:1)
+	fn set_prerequisites(p: u32, ) -> Weight {
+		Weight::from_parts(14_760_000, 3_520)
+			.saturating_add(Weight::from_parts(2_840_000, 2_520).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Certificates CertificateTypes (r:r w:0)
+	/// Storage: Certificates SiteCount (r:1 w:1)
+	/// Storage: Certificates SiteProfiles (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn create_site_profile(r: u32, ) -> Weight {
+		Weight::from_parts(27_310_000, 3_590)
+			.saturating_add(Weight::from_parts(2_870_000, 2_520).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Certificates SiteProfiles (r:1 w:1)
+	/// Storage: Certificates CertificateTypes (r:r w:0)
+	fn update_site_profile(r: u32, ) -> Weight {
+		Weight::from_parts(19_260_000, 3_680)
+			.saturating_add(Weight::from_parts(2_880_000, 2_520).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Certificates SiteProfiles (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn remove_site_profile() -> Weight {
+		Weight::from_parts(25_730_000, 3_680)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Issuers (r:2 w:0)
+	/// Storage: Certificates Delegates (r:1 w:1)
+	/// Storage: Certificates SigningKeys (r:2 w:0)
+	/// Storage: Certificates RetiredKeys (r:2 w:0)
+	/// Storage: Certificates IssuerKeys (r:1 w:0)
+	/// Storage: Certificates IssuerDelegates (r:1 w:1)
+	fn add_delegate() -> Weight {
+		Weight::from_parts(23_650_000, 4_620)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:1)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates IssuerDelegates (r:1 w:1)
+	fn remove_delegate() -> Weight {
+		Weight::from_parts(18_960_000, 4_620)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates SigningKeys (r:2 w:2)
+	/// Storage: Certificates Issuers (r:2 w:0)
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates IssuerKeys (r:2 w:1)
+	/// Storage: Certificates RetiredKeys (r:1 w:1)
+	fn rotate_issuer_key() -> Weight {
+		Weight::from_parts(33_260_000, 9_840)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates IssuanceFees (r:0 w:1)
+	fn set_issuance_fee() -> Weight {
+		Weight::from_parts(14_630_000, 3_520)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:0)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates PendingOffers (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn offer_cert(m: u32, ) -> Weight {
+		Weight::from_parts(29_920_000, 4_120)
+			.saturating_add(Weight::from_parts(1_215, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates PendingOffers (r:1 w:1)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn accept_cert() -> Weight {
+		Weight::from_parts(22_140_000, 4_120)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates PendingOffers (r:1 w:1)
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn reject_cert() -> Weight {
+		Weight::from_parts(27_310_000, 3_780)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Certificates Certificates (r:3 w:3)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates Tombstones (r:0 w:1)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Certificates Endorsements (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:e w:e)
+	fn burn_cert(e: u32, ) -> Weight {
+		Weight::from_parts(31_870_000, 9_160)
+			.saturating_add(Weight::from_parts(4_930_000, 2_603).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: Certificates Accreditors (r:1 w:1)
+	fn register_accreditor() -> Weight {
+		Weight::from_parts(16_720_000, 3_680)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Accreditors (r:1 w:1)
+	fn remove_accreditor() -> Weight {
+		Weight::from_parts(16_050_000, 3_680)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Accreditors (r:2 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Accreditations (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn accredit_issuer() -> Weight {
+		Weight::from_parts(24_380_000, 3_930)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Accreditations (r:1 w:1)
+	fn withdraw_accreditation() -> Weight {
+		Weight::from_parts(15_870_000, 3_560)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates AccreditationRequired (r:0 w:1)
+	fn set_accreditation_required() -> Weight {
+		Weight::from_parts(14_290_000, 3_520)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	fn force_revoke() -> Weight {
+		Weight::from_parts(18_960_000, 3_780)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Issuers (r:2 w:1)
+	/// Storage: Certificates IssuerCertificates (r:1 w:0)
+	/// Storage: Certificates Cascades (r:0 w:1)
+	fn force_suspend_issuer() -> Weight {
+		Weight::from_parts(21_570_000, 3_930)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Cascades (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:c w:c)
+	/// Storage: Certificates Certificates (r:c w:c)
+	/// Storage: Certificates PendingOffers (r:0 w:c)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:c)
+	/// Storage: System Account (r:c w:c)
+	fn process_cascade(c: u32, ) -> Weight {
+		Weight::from_parts(5_120_000, 1_560)
+			.saturating_add(Weight::from_parts(14_870_000, 3_780).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(c.into())))
+	}
+	/// Storage: Certificates Certificates (r:1 w:0)
+	/// Storage: Certificates Endorsements (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn endorse_cert() -> Weight {
+		Weight::from_parts(24_610_000, 5_370)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:0)
+	/// Storage: Certificates Endorsements (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn revoke_endorsement() -> Weight {
+		Weight::from_parts(23_940_000, 5_370)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Certificates UsedNonces (r:1 w:1)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Certificates NonceExpiries (r:0 w:1)
+	fn claim_cert(m: u32, ) -> Weight {
+		Weight::from_parts(81_470_000, 4_120)
+			.saturating_add(Weight::from_parts(1_480, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: Certificates AccountCertificates (r:1 w:0)
+	/// Storage: Certificates Certificates (r:c w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Certificates AccreditationRequired (r:c w:0)
+	/// Storage: Certificates CertificateAccreditations (r:c w:0)
+	fn check_prerequisites(c: u32, ) -> Weight {
+		Weight::from_parts(3_120_000, 1_530)
+			.saturating_add(Weight::from_parts(6_870_000, 3_780).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(c.into())))
+	}
+	/// Storage: Certificates ExpiryCursor (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:b w:e)
+	/// Storage: Certificates Certificates (r:e w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn process_expiries(b: u32, e: u32, ) -> Weight {
+		Weight::from_parts(4_310_000, 1_490)
+			.saturating_add(Weight::from_parts(2_760_000, 2_490).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(7_940_000, 3_780).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: Certificates NonceCursor (r:1 w:1)
+	/// Storage: Certificates NonceExpiries (r:b w:n)
+	/// Storage: Certificates UsedNonces (r:0 w:n)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn process_nonce_expiries(b: u32, n: u32, ) -> Weight {
+		Weight::from_parts(4_180_000, 1_490)
+			.saturating_add(Weight::from_parts(2_760_000, 2_490).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(5_120_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn issue_cert(m: u32, ) -> Weight {
+		Weight::from_parts(32_410_000, 4_120)
+			.saturating_add(Weight::from_parts(1_215, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	fn revoke_cert() -> Weight {
+		Weight::from_parts(21_530_000, 3_780)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Certificates (r:1 w:1)
+	fn suspend_cert() -> Weight {
+		Weight::from_parts(18_940_000, 3_780)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates Certificates (r:1 w:1)
+	fn reinstate_cert() -> Weight {
+		Weight::from_parts(22_170_000, 3_930)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Certificates (r:3 w:2)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:2)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn renew_cert(m: u32, ) -> Weight {
+		Weight::from_parts(46_860_000, 7_540)
+			.saturating_add(Weight::from_parts(1_230, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:1)
+	/// Storage: Certificates CertificateTypes (r:t w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	fn register_issuer(t: u32, ) -> Weight {
+		Weight::from_parts(18_940_000, 3_930)
+			.saturating_add(Weight::from_parts(2_910_000, 2_520).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(t.into())))
+	}
+	/// Storage: Certificates Issuers (r:1 w:1)
+	fn suspend_issuer() -> Weight {
+		Weight::from_parts(16_880_000, 3_930)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Issuers (r:1 w:1)
+	fn reinstate_issuer() -> Weight {
+		Weight::from_parts(16_910_000, 3_930)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Issuers (r:1 w:1)
+	/// Storage: Certificates Accreditations (r:0 w:t)
+	/// Storage: Certificates IssuerDelegates (r:1 w:1)
+	/// Storage: Certificates Delegates (r:0 w:d)
+	/// Storage: Certificates IssuerKeys (r:1 w:1)
+	/// Storage: Certificates SigningKeys (r:0 w:1)
+	/// Storage: Certificates RetiredKeys (r:0 w:2)
+	fn remove_issuer(t: u32, d: u32, ) -> Weight {
+		Weight::from_parts(24_610_000, 9_840)
+			.saturating_add(Weight::from_parts(1_870_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_920_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:1)
+	fn register_certificate_type(f: u32, ) -> Weight {
+		Weight::from_parts(15_630_000, 3_520)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:1)
+	/// Storage: Certificates Prerequisites (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:0 w:1)
+	/// Storage: Certificates AccreditationRequired (r:0 w:1)
+	fn remove_certificate_type() -> Weight {
+		Weight::from_parts(15_210_000, 3_520)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:n w:0)
+	/// Storage: Certificates CertificateTypes (r:n w:0)
+	/// Storage: Certificates Prerequisites (r:n w:0)
+	/// Storage: Certificates AccountCertificates (r:n w:n)
+	/// Storage: Certificates Certificates (r:0 w:n)
+	/// Storage: Certificates CertificateCount (r:n w:n)
+	/// Storage: Certificates IssuerCertificates (r:0 w:n)
+	/// Storage: Certificates ExpiryIndex (r:0 w:n)
+	/// Storage: Certificates IssuanceFees (r:n w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:n w:0)
+	/// Storage: Certificates Accreditors (r:n w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:n)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn issue_batch(n: u32, ) -> Weight {
+		Weight::from_parts(9_870_000, 990)
+			.saturating_add(Weight::from_parts(35_650_000, 4_120).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:c w:c)
+	/// Storage: System Account (r:c w:c)
+	fn prune_certificates(c: u32, ) -> Weight {
+		Weight::from_parts(14_020_000, 1_520)
+			.saturating_add(Weight::from_parts(5_380_000, 3_780).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(c.into())))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn issue_cert_hashed() -> Weight {
+		Weight::from_parts(27_870_000, 4_120)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:p w:0)
+	/// Storage: Certificates Prerequisites (r:0 w:1)
+	fn set_prerequisites(p: u32, ) -> Weight {
+		Weight::from_parts(14_760_000, 3_520)
+			.saturating_add(Weight::from_parts(2_840_000, 2_520).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Certificates CertificateTypes (r:r w:0)
+	/// Storage: Certificates SiteCount (r:1 w:1)
+	/// Storage: Certificates SiteProfiles (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn create_site_profile(r: u32, ) -> Weight {
+		Weight::from_parts(27_310_000, 3_590)
+			.saturating_add(Weight::from_parts(2_870_000, 2_520).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Certificates SiteProfiles (r:1 w:1)
+	/// Storage: Certificates CertificateTypes (r:r w:0)
+	fn update_site_profile(r: u32, ) -> Weight {
+		Weight::from_parts(19_260_000, 3_680)
+			.saturating_add(Weight::from_parts(2_880_000, 2_520).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Certificates SiteProfiles (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn remove_site_profile() -> Weight {
+		Weight::from_parts(25_730_000, 3_680)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Issuers (r:2 w:0)
+	/// Storage: Certificates Delegates (r:1 w:1)
+	/// Storage: Certificates SigningKeys (r:2 w:0)
+	/// Storage: Certificates RetiredKeys (r:2 w:0)
+	/// Storage: Certificates IssuerKeys (r:1 w:0)
+	/// Storage: Certificates IssuerDelegates (r:1 w:1)
+	fn add_delegate() -> Weight {
+		Weight::from_parts(23_650_000, 4_620)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:1)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates IssuerDelegates (r:1 w:1)
+	fn remove_delegate() -> Weight {
+		Weight::from_parts(18_960_000, 4_620)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates SigningKeys (r:2 w:2)
+	/// Storage: Certificates Issuers (r:2 w:0)
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates IssuerKeys (r:2 w:1)
+	/// Storage: Certificates RetiredKeys (r:1 w:1)
+	fn rotate_issuer_key() -> Weight {
+		Weight::from_parts(33_260_000, 9_840)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates IssuanceFees (r:0 w:1)
+	fn set_issuance_fee() -> Weight {
+		Weight::from_parts(14_630_000, 3_520)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:0)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates PendingOffers (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn offer_cert(m: u32, ) -> Weight {
+		Weight::from_parts(29_920_000, 4_120)
+			.saturating_add(Weight::from_parts(1_215, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates PendingOffers (r:1 w:1)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn accept_cert() -> Weight {
+		Weight::from_parts(22_140_000, 4_120)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates PendingOffers (r:1 w:1)
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn reject_cert() -> Weight {
+		Weight::from_parts(27_310_000, 3_780)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Certificates Certificates (r:3 w:3)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates Tombstones (r:0 w:1)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Certificates Endorsements (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:e w:e)
+	fn burn_cert(e: u32, ) -> Weight {
+		Weight::from_parts(31_870_000, 9_160)
+			.saturating_add(Weight::from_parts(4_930_000, 2_603).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: Certificates Accreditors (r:1 w:1)
+	fn register_accreditor() -> Weight {
+		Weight::from_parts(16_720_000, 3_680)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Accreditors (r:1 w:1)
+	fn remove_accreditor() -> Weight {
+		Weight::from_parts(16_050_000, 3_680)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Accreditors (r:2 w:0)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Accreditations (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn accredit_issuer() -> Weight {
+		Weight::from_parts(24_380_000, 3_930)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Accreditations (r:1 w:1)
+	fn withdraw_accreditation() -> Weight {
+		Weight::from_parts(15_870_000, 3_560)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates AccreditationRequired (r:0 w:1)
+	fn set_accreditation_required() -> Weight {
+		Weight::from_parts(14_290_000, 3_520)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	fn force_revoke() -> Weight {
+		Weight::from_parts(18_960_000, 3_780)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Issuers (r:2 w:1)
+	/// Storage: Certificates IssuerCertificates (r:1 w:0)
+	/// Storage: Certificates Cascades (r:0 w:1)
+	fn force_suspend_issuer() -> Weight {
+		Weight::from_parts(21_570_000, 3_930)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Cascades (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:c w:c)
+	/// Storage: Certificates Certificates (r:c w:c)
+	/// Storage: Certificates PendingOffers (r:0 w:c)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:c)
+	/// Storage: System Account (r:c w:c)
+	fn process_cascade(c: u32, ) -> Weight {
+		Weight::from_parts(5_120_000, 1_560)
+			.saturating_add(Weight::from_parts(14_870_000, 3_780).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(c.into())))
+	}
+	/// Storage: Certificates Certificates (r:1 w:0)
+	/// Storage: Certificates Endorsements (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn endorse_cert() -> Weight {
+		Weight::from_parts(24_610_000, 5_370)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Certificates Certificates (r:1 w:0)
+	/// Storage: Certificates Endorsements (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn revoke_endorsement() -> Weight {
+		Weight::from_parts(23_940_000, 5_370)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: Certificates Delegates (r:1 w:0)
+	/// Storage: Certificates SigningKeys (r:1 w:0)
+	/// Storage: Certificates RetiredKeys (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Certificates UsedNonces (r:1 w:1)
+	/// Storage: Certificates Issuers (r:1 w:0)
+	/// Storage: Certificates CertificateTypes (r:1 w:0)
+	/// Storage: Certificates Prerequisites (r:1 w:0)
+	/// Storage: Certificates AccountCertificates (r:1 w:1)
+	/// Storage: Certificates Certificates (r:0 w:1)
+	/// Storage: Certificates CertificateCount (r:1 w:1)
+	/// Storage: Certificates IssuerCertificates (r:0 w:1)
+	/// Storage: Certificates ExpiryIndex (r:0 w:1)
+	/// Storage: Certificates IssuanceFees (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Certificates Accreditations (r:1 w:0)
+	/// Storage: Certificates Accreditors (r:1 w:0)
+	/// Storage: Certificates CertificateAccreditations (r:0 w:1)
+	/// Storage: Certificates NonceExpiries (r:0 w:1)
+	fn claim_cert(m: u32, ) -> Weight {
+		Weight::from_parts(81_470_000, 4_120)
+			.saturating_add(Weight::from_parts(1_480, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: Certificates AccountCertificates (r:1 w:0)
+	/// Storage: Certificates Certificates (r:c w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Certificates AccreditationRequired (r:c w:0)
+	/// Storage: Certificates CertificateAccreditations (r:c w:0)
+	fn check_prerequisites(c: u32, ) -> Weight {
+		Weight::from_parts(3_120_000, 1_530)
+			.saturating_add(Weight::from_parts(6_870_000, 3_780).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(c.into())))
+	}
+	/// Storage: Certificates ExpiryCursor (r:1 w:1)
+	/// Storage: Certificates ExpiryIndex (r:b w:e)
+	/// Storage: Certificates Certificates (r:e w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn process_expiries(b: u32, e: u32, ) -> Weight {
+		Weight::from_parts(4_310_000, 1_490)
+			.saturating_add(Weight::from_parts(2_760_000, 2_490).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(7_940_000, 3_780).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: Certificates NonceCursor (r:1 w:1)
+	/// Storage: Certificates NonceExpiries (r:b w:n)
+	/// Storage: Certificates UsedNonces (r:0 w:n)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn process_nonce_expiries(b: u32, n: u32, ) -> Weight {
+		Weight::from_parts(4_180_000, 1_490)
+			.saturating_add(Weight::from_parts(2_760_000, 2_490).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(5_120_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+}
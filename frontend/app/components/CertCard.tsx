@@ -9,21 +9,28 @@ interface CertCardProps {
 }
 
 export function CertCard({ certificate }: CertCardProps) {
+  const isSuspended = (cert: Certificate) => cert.status === 'Suspended' || cert.status === 'ForceSuspended'
+
   const getStatusColor = (cert: Certificate) => {
-    if (cert.revoked) return 'text-destructive'
-    if (cert.isExpired) return 'text-yellow-500'
+    if (cert.status === 'Revoked') return 'text-destructive'
+    if (isSuspended(cert) || cert.status === 'Superseded') return 'text-muted-foreground'
+    if (cert.isExpired || !cert.prerequisitesMet) return 'text-yellow-500'
     return 'text-green-500'
   }
 
   const getStatusIcon = (cert: Certificate) => {
-    if (cert.revoked) return <ShieldX className="h-6 w-6" />
+    if (cert.status === 'Revoked') return <ShieldX className="h-6 w-6" />
+    if (isSuspended(cert) || cert.status === 'Superseded') return <Shield className="h-6 w-6" />
     if (cert.isExpired) return <Clock className="h-6 w-6" />
+    if (!cert.prerequisitesMet) return <Shield className="h-6 w-6" />
     return <ShieldCheck className="h-6 w-6" />
   }
 
   const getStatusText = (cert: Certificate) => {
-    if (cert.revoked) return 'Revoked'
+    if (cert.status === 'ForceSuspended') return 'Suspended by regulator'
+    if (cert.status !== 'Active') return cert.status
     if (cert.isExpired) return 'Expired'
+    if (!cert.prerequisitesMet) return 'Prerequisite lapsed'
     return 'Active'
   }
 
@@ -63,6 +70,7 @@ export function CertCard({ certificate }: CertCardProps) {
         <div className="flex items-center gap-2 text-sm text-muted-foreground">
           <Shield className="h-4 w-4" />
           <span>ID: {certificate.id}</span>
+          {certificate.isPrivate && <span>· Details held by the holder</span>}
         </div>
       </div>
     </div>
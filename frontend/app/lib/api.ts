@@ -1,7 +1,7 @@
 "use client"
 
 import { ApiPromise, WsProvider } from '@polkadot/api'
-import { Certificate } from './types'
+import { Certificate, ComplianceReport, Endorsement, TrustChain } from './types'
 
 let api: ApiPromise | null = null
 const NODE_URL = process.env.NEXT_PUBLIC_NODE_URL || 'ws://127.0.0.1:9944'
@@ -26,48 +26,70 @@ export async function getAccountCertificates(accountId: string): Promise<Certifi
     }
     
     // Transform the data to match our Certificate interface
-    const currentBlock = await api.query.system.number()
     const now = new Date()
     
-    return rawCertificates.map((cert: any) => {
-      const metadata = JSON.parse(Buffer.from(cert.metadata).toString('utf-8'))
-      const issuedAt = new Date(metadata.issued_at * 1000)
+    // @polkadot/api decodes struct fields in camelCase
+    return Promise.all(rawCertificates.map(async (cert: any) => {
+      // Hashed certificates keep their metadata document off chain with the holder
+      const isPrivate = cert.content.isHashed
+      const metadata = isPrivate
+        ? {}
+        : JSON.parse(Buffer.from(cert.content.asInline).toString('utf-8'))
+      const issuedAt = isPrivate
+        ? await getBlockTime(cert.issuedAt.toNumber())
+        : new Date(metadata.issued_at * 1000)
       
-      // Calculate expiry based on block number difference
-      let expiresAt = null
-      let isExpired = false
+      // Expiry is kept on chain in milliseconds since the Unix epoch (0 if none)
+      const expiresAtMs = Number(cert.expiresAt)
+      const expiresAt = expiresAtMs > 0 ? new Date(expiresAtMs) : null
+      const isExpired = expiresAt !== null && expiresAt <= now
       
-      if (cert.expires_at > 0) {
-        // Approximate expiry date based on 12 second blocks
-        const blockDiff = cert.expires_at - currentBlock.toNumber()
-        const secondsRemaining = blockDiff * 12
-        
-        if (blockDiff <= 0) {
-          isExpired = true
-        }
-        
-        expiresAt = new Date(now.getTime() + (secondsRemaining * 1000))
-      }
+      // Display the canonical type name from the on-chain registry
+      const certType = await getCertificateTypeName(cert.certType.toString())
+      
+      // A certificate in force still lapses when a prerequisite certificate does
+      const status = cert.status.toString()
+      const prerequisitesMet = status !== 'Active' || isExpired
+        ? true
+        : (await api.rpc.certificates.isCertificateEffective(cert.id)).isTrue
       
       return {
         id: cert.id.toString(),
-        name: metadata.name,
-        certType: metadata.cert_type,
+        name: isPrivate ? certType : metadata.name,
+        certType,
         owner: cert.owner.toString(),
         issuer: cert.issuer.toString(),
+        issuedBy: cert.issuedBy.toString(),
         issuedAt,
         expiresAt,
-        revoked: cert.revoked,
+        status,
         isExpired,
+        prerequisitesMet,
+        isPrivate,
         metadata
       }
-    })
+    }))
   } catch (error) {
     console.error('Error fetching certificates:', error)
     throw new Error('Failed to fetch certificates from the blockchain')
   }
 }
 
+/** Time a block was produced, read from the timestamp inherent in its body */
+export async function getBlockTime(blockNumber: number): Promise<Date> {
+  const api = await getApi()
+  const hash = await api.rpc.chain.getBlockHash(blockNumber)
+  const { block } = await api.rpc.chain.getBlock(hash)
+  const setTime = block.extrinsics.find(({ method }) => method.section === 'timestamp' && method.method === 'set')
+  
+  if (!setTime) {
+    throw new Error(`Block ${blockNumber} has no timestamp`)
+  }
+  
+  // The timestamp is in milliseconds since the Unix epoch
+  return new Date(Number(setTime.method.args[0].toString()))
+}
+
 export async function isCertificateValid(certId: string): Promise<boolean> {
   try {
     const api = await getApi()
@@ -76,4 +98,94 @@ export async function isCertificateValid(certId: string): Promise<boolean> {
     console.error('Error checking certificate validity:', error)
     throw new Error('Failed to check certificate validity')
   }
-} 
\ No newline at end of file
+}
+
+export async function verifyMetadata(certId: string, document: string): Promise<boolean> {
+  try {
+    const api = await getApi()
+    return await api.rpc.certificates.verifyMetadata(certId, Buffer.from(document, 'utf-8'))
+  } catch (error) {
+    console.error('Error verifying certificate metadata:', error)
+    throw new Error('Failed to verify certificate metadata')
+  }
+}
+
+export async function checkCompliance(accountId: string, siteId: string): Promise<ComplianceReport | null> {
+  try {
+    const api = await getApi()
+    const report: any = await api.rpc.certificates.checkCompliance(accountId, siteId)
+    
+    if (report.isNone) {
+      return null
+    }
+    
+    const { compliant, missing, expiring } = report.unwrap()
+    return {
+      compliant: compliant.isTrue,
+      missing: await Promise.all(missing.map((typeId: any) => getCertificateTypeName(typeId.toString()))),
+      expiring: await Promise.all(expiring.map(async ([typeId, certId]: any) => ({
+        certType: await getCertificateTypeName(typeId.toString()),
+        certId: certId.toString()
+      })))
+    }
+  } catch (error) {
+    console.error('Error checking site compliance:', error)
+    throw new Error('Failed to check site compliance')
+  }
+}
+
+export async function getTrustChain(certId: string): Promise<TrustChain | null> {
+  try {
+    const api = await getApi()
+    const chain: any = await api.rpc.certificates.getTrustChain(certId)
+    
+    if (chain.isNone) {
+      return null
+    }
+    
+    const { issuer, issuerActive, accreditor, accreditationPeriod, accreditorRegistered, accreditationRequired } =
+      chain.unwrap()
+    // The accreditation period is kept on chain in milliseconds since the Unix epoch
+    const period = accreditationPeriod.isSome ? accreditationPeriod.unwrap() : null
+    return {
+      issuer: issuer.toString(),
+      issuerActive: issuerActive.isTrue,
+      accreditor: accreditor.isSome ? accreditor.unwrap().toString() : null,
+      accreditedFrom: period ? new Date(Number(period[0])) : null,
+      accreditedUntil: period ? new Date(Number(period[1])) : null,
+      accreditorRegistered: accreditorRegistered.isTrue,
+      accreditationRequired: accreditationRequired.isTrue
+    }
+  } catch (error) {
+    console.error('Error fetching certificate trust chain:', error)
+    throw new Error('Failed to fetch certificate trust chain')
+  }
+}
+
+export async function getEndorsements(certId: string): Promise<Endorsement[]> {
+  try {
+    const api = await getApi()
+    const endorsements: any = await api.rpc.certificates.getEndorsements(certId)
+    
+    // Claims are kept off chain; only their hash is recorded
+    return endorsements.map((endorsement: any) => ({
+      endorser: endorsement.endorser.toString(),
+      claimHash: endorsement.claimHash.toHex(),
+      endorsedAt: endorsement.endorsedAt.toNumber()
+    }))
+  } catch (error) {
+    console.error('Error fetching certificate endorsements:', error)
+    throw new Error('Failed to fetch certificate endorsements')
+  }
+}
+
+export async function getCertificateTypeName(typeId: string): Promise<string> {
+  const api = await getApi()
+  const typeInfo: any = await api.query.certificates.certificateTypes(typeId)
+  
+  if (typeInfo.isNone) {
+    return `Type ${typeId}`
+  }
+  
+  return Buffer.from(typeInfo.unwrap().name).toString('utf-8')
+}
@@ -1,12 +1,39 @@
+export type CertificateStatus = 'Active' | 'Suspended' | 'Revoked' | 'Superseded' | 'Pending' | 'ForceSuspended'
+
+export interface ComplianceReport {
+  compliant: boolean;
+  missing: string[];
+  expiring: { certType: string; certId: string }[];
+}
+
+export interface TrustChain {
+  issuer: string;
+  issuerActive: boolean;
+  accreditor: string | null;
+  accreditedFrom: Date | null;
+  accreditedUntil: Date | null;
+  accreditorRegistered: boolean;
+  accreditationRequired: boolean;
+}
+
+export interface Endorsement {
+  endorser: string;
+  claimHash: string;
+  endorsedAt: number;
+}
+
 export interface Certificate {
   id: string;
   name: string;
   certType: string;
   owner: string;
   issuer: string;
+  issuedBy: string;
   issuedAt: Date;
   expiresAt: Date | null;
-  revoked: boolean;
+  status: CertificateStatus;
   isExpired: boolean;
+  prerequisitesMet: boolean;
+  isPrivate: boolean;
   metadata: Record<string, any>;
 } 
\ No newline at end of file
@@ -1,18 +1,21 @@
 "use client"
 
 import { useState, useEffect } from 'react'
-import { useParams } from 'next/navigation'
+import { useParams, useSearchParams } from 'next/navigation'
 import { CertList } from '@/app/components/CertList'
-import { getAccountCertificates } from '@/app/lib/api'
-import { Certificate } from '@/app/lib/types'
-import { Shield, User, ArrowLeft } from 'lucide-react'
+import { checkCompliance, getAccountCertificates } from '@/app/lib/api'
+import { Certificate, ComplianceReport } from '@/app/lib/types'
+import { Shield, ShieldCheck, ShieldX, User, ArrowLeft } from 'lucide-react'
 import Link from 'next/link'
 
 export default function VerifyAccount() {
   const params = useParams()
   const account = params.account as string
+  // Optional site profile to check the account against, e.g. /verify/<account>?site=3
+  const site = useSearchParams().get('site')
   
   const [certificates, setCertificates] = useState<Certificate[]>([])
+  const [compliance, setCompliance] = useState<ComplianceReport | null>(null)
   const [isLoading, setIsLoading] = useState(true)
   const [error, setError] = useState<string | null>(null)
 
@@ -24,6 +27,10 @@ export default function VerifyAccount() {
         
         const certs = await getAccountCertificates(account)
         setCertificates(certs)
+        
+        if (site) {
+          setCompliance(await checkCompliance(account, site))
+        }
       } catch (err) {
         console.error('Error loading certificates:', err)
         setError('Failed to load certificates from the blockchain')
@@ -35,7 +42,7 @@ export default function VerifyAccount() {
     if (account) {
       loadCertificates()
     }
-  }, [account])
+  }, [account, site])
 
   // Format the account address for display (truncate middle)
   const formatAddress = (address: string) => {
@@ -79,6 +86,23 @@ export default function VerifyAccount() {
           <div className="h-1 w-24 bg-primary my-4"></div>
         </div>
         
+        {compliance && (
+          <div className={`mb-8 rounded-lg border p-4 ${compliance.compliant ? 'text-green-500' : 'text-destructive'}`}>
+            <div className="flex items-center gap-2 font-medium">
+              {compliance.compliant ? <ShieldCheck className="h-5 w-5" /> : <ShieldX className="h-5 w-5" />}
+              <span>{compliance.compliant ? `Cleared for site ${site}` : `Not cleared for site ${site}`}</span>
+            </div>
+            {compliance.missing.length > 0 && (
+              <p className="mt-2 text-sm">Missing: {compliance.missing.join(', ')}</p>
+            )}
+            {compliance.expiring.length > 0 && (
+              <p className="mt-2 text-sm">
+                Expiring too soon: {compliance.expiring.map((e) => `${e.certType} (ID ${e.certId})`).join(', ')}
+              </p>
+            )}
+          </div>
+        )}
+        
         <CertList 
           certificates={certificates}
           isLoading={isLoading}
@@ -1,12 +1,19 @@
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
-use clap::{Parser, Subcommand};
+use chrono::{NaiveDate, TimeZone, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
 use codec::{Decode, Encode};
 use qrcode::QrCode;
 use qrcode::render::unicode;
 use serde::{Deserialize, Serialize};
-use sp_core::{crypto::Ss58Codec, sr25519::Pair};
-use std::path::PathBuf;
+use sp_core::{
+    blake2_256,
+    crypto::{AccountId32, Ss58Codec},
+    hexdisplay::HexDisplay,
+    sr25519::{self, Pair},
+    Pair as _, H256,
+};
+use std::path::{Path, PathBuf};
+use subxt::utils::MultiAddress;
 use substrate_api_client::{
     rpc::WsRpcClient,
     Api, XtStatus,
@@ -15,6 +22,10 @@ use tracing::{info, error};
 
 const DEFAULT_NODE_URL: &str = "ws://127.0.0.1:9944";
 
+/// Account argument of calls taking `AccountIdLookupOf<T>`, which the runtime resolves from a
+/// `MultiAddress`
+type Address = MultiAddress<AccountId32, ()>;
+
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -36,20 +47,30 @@ enum Commands {
     /// Issue a new certificate
     Issue {
         /// Address of the certificate recipient
-        #[arg(short, long)]
+        #[arg(short = 'r', long)]
         to: String,
 
         /// Name of the certificate
         #[arg(short, long)]
         cert: String,
 
-        /// Type of the certificate (e.g., WHMIS, Rigging, Tower Crane)
+        /// ID of the certificate type in the on-chain registry (e.g., WHMIS, Rigging, Tower Crane)
         #[arg(short, long)]
-        r#type: String,
+        r#type: u32,
 
-        /// Expiry date in YYYY-MM-DD format
+        /// Expiry date in YYYY-MM-DD format (default: the type's validity period)
         #[arg(short, long)]
-        expiry: String,
+        expiry: Option<String>,
+
+        /// Keep the metadata document off chain, saving it to this path for the holder;
+        /// only its hash is stored on chain. The document carries a random salt, so it cannot be
+        /// recreated: the holder must keep the file to prove the certificate's contents
+        #[arg(short, long)]
+        document: Option<PathBuf>,
+
+        /// Content-addressed locator of the off-chain document (e.g. an IPFS CID)
+        #[arg(short, long, requires = "document")]
+        locator: Option<String>,
 
         /// Generate QR code for verification
         #[arg(short, long)]
@@ -58,6 +79,27 @@ enum Commands {
         /// Path to save the QR code image (default: credential_<id>.png)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Address of the issuer identity, when signing with a rotated key or as a delegate
+        /// (default: the signing key's own account)
+        #[arg(short, long)]
+        issuer: Option<String>,
+    },
+
+    /// Issue certificates of one type to a whole cohort in a single transaction
+    IssueBatch {
+        /// ID of the certificate type in the on-chain registry
+        #[arg(short, long)]
+        r#type: u32,
+
+        /// Address of the issuer identity, when signing with a rotated key or as a delegate
+        /// (default: the signing key's own account)
+        #[arg(short, long)]
+        issuer: Option<String>,
+
+        /// Path to a JSON file listing the cohort: [{"to": ..., "name": ..., "expiry": "YYYY-MM-DD"}]
+        #[arg(short, long)]
+        file: PathBuf,
     },
 
     /// Revoke an existing certificate
@@ -65,70 +107,234 @@ enum Commands {
         /// ID of the certificate to revoke
         #[arg(short, long)]
         id: u32,
+
+        /// Reason for the revocation
+        #[arg(short, long, value_enum)]
+        reason: RevocationReason,
+
+        /// Free-text note explaining the revocation (only its hash is stored on chain)
+        #[arg(short, long)]
+        note: Option<String>,
+    },
+
+    /// Replace an issuer's signing key; the issuer keeps its identity and certificates
+    RotateKey {
+        /// Address of the issuer identity (default: the issuer the signing key belongs to)
+        #[arg(short, long)]
+        issuer: Option<String>,
+
+        /// Address of the new signing key
+        #[arg(short, long)]
+        new_key: String,
     },
+
+    /// Sign a voucher off chain that the holder claims a certificate with from their own account
+    Voucher {
+        /// Address of the certificate holder, the only account that can claim the voucher
+        #[arg(short = 'r', long)]
+        to: String,
+
+        /// Name of the certificate
+        #[arg(short, long)]
+        cert: String,
+
+        /// ID of the certificate type in the on-chain registry
+        #[arg(short, long)]
+        r#type: u32,
+
+        /// Expiry date in YYYY-MM-DD format (default: the type's validity period)
+        #[arg(short, long)]
+        expiry: Option<String>,
+
+        /// Address of the issuer identity (default: the signing key's own account)
+        #[arg(short, long)]
+        issuer: Option<String>,
+
+        /// Voucher number, used once per issuer (default: the current time in milliseconds)
+        #[arg(long)]
+        nonce: Option<u64>,
+
+        /// Number of days the voucher can be claimed for, up to the chain's maximum voucher validity
+        #[arg(long, default_value_t = 30)]
+        valid_days: u32,
+
+        /// Path to save the voucher file (default: voucher_<nonce>.json)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+
+        /// Generate a QR code encoding the voucher file
+        #[arg(short, long)]
+        generate_qr: bool,
+
+        /// Path to save the QR code image (default: voucher_<nonce>.png)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Mirrors `pallet_certificates::RevocationReason`
+#[derive(Debug, Clone, Copy, ValueEnum, Encode, Decode)]
+enum RevocationReason {
+    IssuedInError,
+    Fraud,
+    FailedReassessment,
+    Superseded,
+    HolderRequest,
+}
+
+/// Mirrors `pallet_certificates::Voucher`
+#[derive(Debug, Encode, Decode)]
+struct Voucher {
+    issuer: AccountId32,
+    recipient: AccountId32,
+    cert_type: u32,
+    metadata: Vec<u8>,
+    expires_at: Option<u64>,
+    nonce: u64,
+    valid_until: u64,
+}
+
+/// Mirrors the sr25519 variant of `sp_runtime::MultiSignature`, the chain's off-chain signature
+#[derive(Debug, Encode, Decode)]
+enum MultiSignature {
+    #[codec(index = 1)]
+    Sr25519(sr25519::Signature),
+}
+
+/// Voucher file handed to the holder, whose wallet submits its fields to `claim_cert`
+#[derive(Debug, Serialize, Deserialize)]
+struct VoucherFile {
+    /// SCALE-encoded voucher, hex
+    voucher: String,
+    /// Address of the key that signed the voucher
+    signer: String,
+    /// SCALE-encoded `MultiSignature` of the voucher payload, hex
+    signature: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Encode, Decode)]
 struct CertificateMetadata {
     name: String,
-    cert_type: String,
     issuer: String,
     issued_at: u64,
+    /// Random 32 bytes, hex, in off-chain documents so their on-chain hash cannot be brute-forced
+    /// from the other, guessable fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    salt: Option<String>,
 }
 
-async fn issue_certificate(
-    api: &Api<Pair, WsRpcClient>,
-    to: &str,
-    cert_name: &str,
-    cert_type: &str,
-    expiry: &str,
-    generate_qr: bool,
-    output: Option<PathBuf>,
-) -> Result<()> {
-    // Parse address
-    let to_account = to.parse()
-        .context("Failed to parse recipient address")?;
+/// One entry of a cohort file for `issue-batch`
+#[derive(Debug, Deserialize)]
+struct BatchEntry {
+    to: String,
+    name: String,
+    expiry: Option<String>,
+}
 
-    // Parse expiry date
+/// Convert an optional YYYY-MM-DD expiry date into milliseconds since the Unix epoch
+///
+/// The certificate expires at the start of that date (UTC). Returns `None` when no date is
+/// given so the chain applies the type's default validity.
+fn expiry_to_millis(expiry: Option<&str>) -> Result<Option<u64>> {
+    let Some(expiry) = expiry else {
+        return Ok(None);
+    };
+    
     let expiry_date = NaiveDate::parse_from_str(expiry, "%Y-%m-%d")
         .context("Failed to parse expiry date. Use YYYY-MM-DD format")?;
     
-    // Convert to block number (approximation: 1 day = 7200 blocks at 12-second block time)
-    let now = chrono::Local::now().date_naive();
-    let days_until_expiry = expiry_date.signed_duration_since(now).num_days();
-    if days_until_expiry < 0 {
+    let expires_at = Utc.from_utc_datetime(&expiry_date.and_hms_opt(0, 0, 0).unwrap());
+    if expires_at <= Utc::now() {
         anyhow::bail!("Expiry date cannot be in the past");
     }
     
-    let blocks_until_expiry = (days_until_expiry as u32) * 7200;
-    let current_block = api.get_block_number(None)
-        .context("Failed to get current block number")?;
-    let expiry_block = current_block + blocks_until_expiry;
+    Ok(Some(expires_at.timestamp_millis() as u64))
+}
 
-    // Create metadata
+/// Resolve the issuer identity to record in certificate metadata, defaulting to the signing key's
+/// own account
+fn issuer_identity(api: &Api<Pair, WsRpcClient>, issuer: Option<&str>) -> Result<AccountId32> {
+    match issuer {
+        Some(issuer) => issuer.parse().context("Failed to parse issuer address"),
+        None => Ok(api.signer().unwrap().public().into()),
+    }
+}
+
+/// Build the JSON metadata document for a certificate, salted if it is kept off chain
+///
+/// `issuer` is the issuer identity, which differs from the signing key once the key is rotated
+/// or when a delegate signs.
+fn certificate_metadata(issuer: &AccountId32, cert_name: &str, salted: bool) -> Result<Vec<u8>> {
     let metadata = CertificateMetadata {
         name: cert_name.to_string(),
-        cert_type: cert_type.to_string(),
-        issuer: api.signer().unwrap().public().to_ss58check(),
+        issuer: issuer.to_ss58check(),
         issued_at: chrono::Utc::now().timestamp() as u64,
+        salt: salted.then(|| format!("0x{}", HexDisplay::from(&rand::random::<[u8; 32]>()))),
     };
     
     let metadata_json = serde_json::to_string(&metadata)
         .context("Failed to serialize metadata")?;
+    Ok(metadata_json.into_bytes())
+}
+
+async fn issue_certificate(
+    api: &Api<Pair, WsRpcClient>,
+    to: &str,
+    cert_name: &str,
+    cert_type: u32,
+    expiry: Option<&str>,
+    document: Option<&PathBuf>,
+    locator: Option<&str>,
+    generate_qr: bool,
+    output: Option<PathBuf>,
+    issuer: Option<&str>,
+) -> Result<()> {
+    // Parse address
+    let to_account: Address = MultiAddress::Id(to.parse()
+        .context("Failed to parse recipient address")?);
+
+    // Parse expiry date
+    let expires_at = expiry_to_millis(expiry)?;
+
+    // Create metadata
+    let issuer = issuer_identity(api, issuer)?;
+    let metadata = certificate_metadata(&issuer, cert_name, document.is_some())?;
 
     // Prepare and send the transaction
     info!("Issuing certificate to {}", to);
-    info!("Certificate: {} ({})", cert_name, cert_type);
-    info!("Expires at block: {}", expiry_block);
+    info!("Certificate: {} (type {})", cert_name, cert_type);
+    match expiry {
+        Some(date) => info!("Expires: {} (UTC)", date),
+        None => info!("Expiry: type default"),
+    }
     
-    let xt = api.create_signed(
-        subxt::tx::Payload::new(
-            "Certificates",
-            "issue_cert",
-            (to_account, metadata_json.as_bytes().to_vec(), expiry_block),
+    let xt = match document {
+        Some(path) => {
+            // Hand the document to the holder; the chain only commits to its hash
+            std::fs::write(path, &metadata)
+                .context(format!("Failed to write metadata document to {:?}", path))?;
+            let content_hash = H256::from(blake2_256(&metadata));
+            info!("Metadata document saved to {:?} (hash {:?})", path, content_hash);
+            info!("Give the document to the holder to keep; its salt cannot be recovered from the chain");
+            
+            api.create_signed(
+                subxt::tx::Payload::new(
+                    "Certificates",
+                    "issue_cert_hashed",
+                    (to_account, cert_type, content_hash, locator.map(|l| l.as_bytes().to_vec()), expires_at),
+                ),
+                subxt::tx::PairSigner::new(api.signer().unwrap().clone()),
+            )
+        }
+        None => api.create_signed(
+            subxt::tx::Payload::new(
+                "Certificates",
+                "issue_cert",
+                (to_account, cert_type, metadata, expires_at),
+            ),
+            subxt::tx::PairSigner::new(api.signer().unwrap().clone()),
         ),
-        subxt::tx::PairSigner::new(api.signer().unwrap().clone()),
-    );
+    };
     
     let tx_hash = api.send_extrinsic(xt.hex(), XtStatus::InBlock)
         .context("Failed to send transaction")?;
@@ -146,17 +352,71 @@ async fn issue_certificate(
     Ok(())
 }
 
+async fn issue_batch(
+    api: &Api<Pair, WsRpcClient>,
+    cert_type: u32,
+    file: &PathBuf,
+    issuer: Option<&str>,
+) -> Result<()> {
+    let issuer = issuer_identity(api, issuer)?;
+    let contents = std::fs::read_to_string(file)
+        .context(format!("Failed to read cohort file {:?}", file))?;
+    let entries: Vec<BatchEntry> = serde_json::from_str(&contents)
+        .context("Failed to parse cohort file")?;
+    
+    if entries.is_empty() {
+        anyhow::bail!("Cohort file is empty");
+    }
+    
+    let mut certificates = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let to_account: Address = MultiAddress::Id(entry.to.parse()
+            .context(format!("Failed to parse recipient address {}", entry.to))?);
+        certificates.push((
+            to_account,
+            certificate_metadata(&issuer, &entry.name, false)?,
+            expiry_to_millis(entry.expiry.as_deref())?,
+        ));
+    }
+    
+    info!("Issuing {} certificates of type {}", certificates.len(), cert_type);
+    
+    let xt = api.create_signed(
+        subxt::tx::Payload::new(
+            "Certificates",
+            "issue_batch",
+            (cert_type, certificates),
+        ),
+        subxt::tx::PairSigner::new(api.signer().unwrap().clone()),
+    );
+    
+    let tx_hash = api.send_extrinsic(xt.hex(), XtStatus::InBlock)
+        .context("Failed to send transaction")?;
+    
+    info!("Batch issued! Transaction hash: {}", tx_hash);
+    
+    Ok(())
+}
+
 async fn revoke_certificate(
     api: &Api<Pair, WsRpcClient>,
     cert_id: u32,
+    reason: RevocationReason,
+    note: Option<&str>,
 ) -> Result<()> {
-    info!("Revoking certificate with ID: {}", cert_id);
+    info!("Revoking certificate with ID: {} ({:?})", cert_id, reason);
+    
+    // Only a hash of the note goes on chain; keep the note itself for auditors
+    let note_hash = note.map(|note| H256::from(blake2_256(note.as_bytes())));
+    if let Some(hash) = note_hash {
+        info!("Revocation note hash: {:?}", hash);
+    }
     
     let xt = api.create_signed(
         subxt::tx::Payload::new(
             "Certificates",
             "revoke_cert",
-            (cert_id,),
+            (cert_id, reason, note_hash),
         ),
         subxt::tx::PairSigner::new(api.signer().unwrap().clone()),
     );
@@ -169,12 +429,132 @@ async fn revoke_certificate(
     Ok(())
 }
 
+async fn rotate_issuer_key(
+    api: &Api<Pair, WsRpcClient>,
+    issuer: Option<&str>,
+    new_key: &str,
+) -> Result<()> {
+    let signer = api.signer().unwrap().public().to_ss58check();
+    let issuer = issuer.unwrap_or(&signer);
+    let issuer_address: Address = MultiAddress::Id(issuer.parse()
+        .context("Failed to parse issuer address")?);
+    let new_key_address: Address = MultiAddress::Id(new_key.parse()
+        .context("Failed to parse new key address")?);
+    
+    info!("Rotating signing key of issuer {} to {}", issuer, new_key);
+    
+    let xt = api.create_signed(
+        subxt::tx::Payload::new(
+            "Certificates",
+            "rotate_issuer_key",
+            (issuer_address, new_key_address),
+        ),
+        subxt::tx::PairSigner::new(api.signer().unwrap().clone()),
+    );
+    
+    let tx_hash = api.send_extrinsic(xt.hex(), XtStatus::InBlock)
+        .context("Failed to send transaction")?;
+    
+    info!("Issuer key rotated! Transaction hash: {}", tx_hash);
+    info!("Sign with the new key from now on; the old key can no longer act for the issuer");
+    
+    Ok(())
+}
+
+async fn create_voucher(
+    api: &Api<Pair, WsRpcClient>,
+    to: &str,
+    cert_name: &str,
+    cert_type: u32,
+    expiry: Option<&str>,
+    issuer: Option<&str>,
+    nonce: Option<u64>,
+    valid_days: u32,
+    file: Option<PathBuf>,
+    generate_qr: bool,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let signer = api.signer().unwrap();
+    let signer_address = signer.public().to_ss58check();
+    let issuer = issuer_identity(api, issuer)?;
+    let recipient: AccountId32 = to.parse()
+        .context("Failed to parse recipient address")?;
+    
+    let now = Utc::now();
+    let valid_until = now + chrono::Duration::days(valid_days.into());
+    let voucher = Voucher {
+        metadata: certificate_metadata(&issuer, cert_name, false)?,
+        issuer,
+        recipient,
+        cert_type,
+        expires_at: expiry_to_millis(expiry)?,
+        nonce: nonce.unwrap_or(now.timestamp_millis() as u64),
+        valid_until: valid_until.timestamp_millis() as u64,
+    };
+    
+    // A voucher valid for longer than the chain allows could never be claimed
+    let max_voucher_validity: u64 = api.get_constant("Certificates", "MaxVoucherValidity")
+        .context("Failed to read the maximum voucher validity from the chain")?;
+    let max_valid_days = chrono::Duration::milliseconds(max_voucher_validity as i64).num_days();
+    if i64::from(valid_days) > max_valid_days {
+        anyhow::bail!(
+            "Voucher would be valid for {} days, over the chain's limit of {} days",
+            valid_days,
+            max_valid_days
+        );
+    }
+    
+    // A voucher whose metadata is over the chain's limit could never be claimed
+    let max_metadata_length: u32 = api.get_constant("Certificates", "MaxMetadataLength")
+        .context("Failed to read the maximum metadata length from the chain")?;
+    if voucher.metadata.len() > max_metadata_length as usize {
+        anyhow::bail!(
+            "Certificate metadata is {} bytes, over the chain's limit of {}",
+            voucher.metadata.len(),
+            max_metadata_length
+        );
+    }
+    
+    // The chain checks the signature against its genesis hash and the voucher, so a voucher
+    // cannot be claimed on another network
+    let payload = (api.genesis_hash(), &voucher).encode();
+    let signature = MultiSignature::Sr25519(signer.sign(&payload));
+    
+    info!("Voucher {} for certificate: {} (type {})", voucher.nonce, cert_name, cert_type);
+    info!("Claimable by {} until {} (UTC)", to, valid_until.format("%Y-%m-%d %H:%M"));
+    
+    let voucher_file = VoucherFile {
+        voucher: format!("0x{}", HexDisplay::from(&voucher.encode())),
+        signer: signer_address,
+        signature: format!("0x{}", HexDisplay::from(&signature.encode())),
+    };
+    let voucher_json = serde_json::to_string_pretty(&voucher_file)?;
+    let path = file.unwrap_or_else(|| PathBuf::from(format!("voucher_{}.json", voucher.nonce)));
+    std::fs::write(&path, &voucher_json)
+        .context(format!("Failed to write voucher to {:?}", path))?;
+    info!("Voucher saved to {:?}", path);
+    
+    // The QR code carries the voucher file itself for the holder's wallet to scan
+    if generate_qr {
+        let output = output.unwrap_or_else(|| PathBuf::from(format!("voucher_{}.png", voucher.nonce)));
+        render_qr_code(&voucher_json, "Scan this QR code to load the voucher:", &output)?;
+    }
+    
+    Ok(())
+}
+
 fn generate_qr_code(account: &str, cert_id: u32, output_path: Option<PathBuf>) -> Result<()> {
     // Create URL for verification
     let verify_url = format!("https://verisite.io/verify/{}", account);
+    let output_path = output_path.unwrap_or_else(|| PathBuf::from(format!("credential_{}.png", cert_id)));
     
+    render_qr_code(&verify_url, "Scan this QR code to verify the credential:", &output_path)
+}
+
+/// Print a QR code encoding `content` to the terminal and save it as an image
+fn render_qr_code(content: &str, caption: &str, output_path: &Path) -> Result<()> {
     // Generate QR code
-    let code = QrCode::new(verify_url.as_bytes())
+    let code = QrCode::new(content.as_bytes())
         .context("Failed to generate QR code")?;
     
     // Output QR code to terminal
@@ -183,23 +563,14 @@ fn generate_qr_code(account: &str, cert_id: u32, output_path: Option<PathBuf>) -
         .light_color(unicode::Dense1x2::Dark)
         .build();
     
-    println!("\nScan this QR code to verify the credential:\n");
+    println!("\n{}\n", caption);
     println!("{}", image);
     
-    // Save QR code image if requested
-    if let Some(path) = output_path {
-        let output_path = path;
-        let img = code.render::<image::Luma<u8>>().build();
-        img.save(&output_path)
-            .context(format!("Failed to save QR code to {:?}", output_path))?;
-        info!("QR code saved to {:?}", output_path);
-    } else {
-        let default_path = format!("credential_{}.png", cert_id);
-        let img = code.render::<image::Luma<u8>>().build();
-        img.save(&default_path)
-            .context(format!("Failed to save QR code to {}", default_path))?;
-        info!("QR code saved to {}", default_path);
-    }
+    // Save QR code image
+    let img = code.render::<image::Luma<u8>>().build();
+    img.save(output_path)
+        .context(format!("Failed to save QR code to {:?}", output_path))?;
+    info!("QR code saved to {:?}", output_path);
     
     Ok(())
 }
@@ -228,15 +599,74 @@ async fn main() -> Result<()> {
             cert,
             r#type,
             expiry,
+            document,
+            locator,
             generate_qr,
             output,
+            issuer,
         } => {
-            issue_certificate(&api, &to, &cert, &r#type, &expiry, generate_qr, output).await?;
+            issue_certificate(
+                &api,
+                &to,
+                &cert,
+                r#type,
+                expiry.as_deref(),
+                document.as_ref(),
+                locator.as_deref(),
+                generate_qr,
+                output,
+                issuer.as_deref(),
+            )
+            .await?;
+        }
+        Commands::IssueBatch { r#type, issuer, file } => {
+            issue_batch(&api, r#type, &file, issuer.as_deref()).await?;
+        }
+        Commands::Revoke { id, reason, note } => {
+            revoke_certificate(&api, id, reason, note.as_deref()).await?;
+        }
+        Commands::RotateKey { issuer, new_key } => {
+            rotate_issuer_key(&api, issuer.as_deref(), &new_key).await?;
         }
-        Commands::Revoke { id } => {
-            revoke_certificate(&api, id).await?;
+        Commands::Voucher {
+            to,
+            cert,
+            r#type,
+            expiry,
+            issuer,
+            nonce,
+            valid_days,
+            file,
+            generate_qr,
+            output,
+        } => {
+            create_voucher(
+                &api,
+                &to,
+                &cert,
+                r#type,
+                expiry.as_deref(),
+                issuer.as_deref(),
+                nonce,
+                valid_days,
+                file,
+                generate_qr,
+                output,
+            )
+            .await?;
         }
     }
     
     Ok(())
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_arguments_are_consistent() {
+        Cli::command().debug_assert();
+    }
+}